@@ -0,0 +1,58 @@
+extern crate discord_next;
+extern crate dotenv;
+extern crate envy;
+extern crate tokio;
+#[macro_use]
+extern crate serde_derive;
+
+use async_trait::async_trait;
+use discord_next::{model, rest_client::NewMessage, Context, EventHandler};
+
+#[derive(Deserialize, Debug)]
+struct EnvVars {
+    #[serde(rename = "discord_bot_token")]
+    bot_token: String,
+}
+
+const ACTIVATOR: &str = "!echo";
+
+struct Echo;
+
+#[async_trait]
+impl EventHandler for Echo {
+    async fn ready(&self, _ctx: Context, ready: model::Ready) -> Result<(), discord_next::Error> {
+        println!("logged in as {}", ready.user.username);
+        Ok(())
+    }
+
+    async fn message_create(
+        &self,
+        ctx: Context,
+        msg: model::Message,
+    ) -> Result<(), discord_next::Error> {
+        if msg.content.starts_with(ACTIVATOR) {
+            let cmd: String = msg.content[ACTIVATOR.len()..].trim().to_owned();
+            ctx.client
+                .send_message(msg.channel_id, NewMessage::text(cmd))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+
+    let vars = envy::from_env::<EnvVars>().unwrap();
+
+    let conn = match discord_next::Connection::connect(vars.bot_token).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            println!("snafu: {}", e);
+            return;
+        }
+    };
+    let res = conn.run_handler(Echo).await;
+    println!("Bot closed, res: {:?}", res);
+}
@@ -0,0 +1,50 @@
+extern crate discord_next;
+extern crate dotenv;
+extern crate envy;
+extern crate tokio;
+#[macro_use]
+extern crate serde_derive;
+
+use futures::StreamExt;
+
+#[derive(Deserialize, Debug)]
+struct EnvVars {
+    #[serde(rename = "discord_bot_token")]
+    bot_token: String,
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+
+    let vars = envy::from_env::<EnvVars>().unwrap();
+
+    let subscriber = tracing_subscriber::fmt::Subscriber::builder()
+        .with_max_level(tracing::Level::DEBUG)
+        .finish();
+    let _ = tracing::subscriber::set_global_default(subscriber);
+
+    let manager = match discord_next::ShardManager::new(vars.bot_token).await {
+        Ok(manager) => manager,
+        Err(e) => {
+            println!("snafu: {}", e);
+            return;
+        }
+    };
+    println!("starting {} shards", manager.shard_count());
+    let mut events = match manager.start() {
+        Ok(events) => events,
+        Err(e) => {
+            println!("snafu: {}", e);
+            return;
+        }
+    };
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(discord_next::ShardEvent { shard_id, event }) => {
+                println!("shard {}: {:?}", shard_id, event)
+            }
+            Err(e) => println!("shard error: {}", e),
+        }
+    }
+}
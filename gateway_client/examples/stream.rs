@@ -0,0 +1,61 @@
+extern crate discord_next;
+extern crate dotenv;
+extern crate envy;
+extern crate tokio;
+#[macro_use]
+extern crate serde_derive;
+
+use futures::StreamExt;
+
+#[derive(Deserialize, Debug)]
+struct EnvVars {
+    #[serde(rename = "discord_bot_token")]
+    bot_token: String,
+}
+
+const ACTIVATOR: &str = "!echo";
+
+#[tokio::main]
+async fn main() {
+    dotenv::dotenv().ok();
+
+    let vars = envy::from_env::<EnvVars>().unwrap();
+
+    let conn = match discord_next::Connection::connect(vars.bot_token.clone()).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            println!("snafu: {}", e);
+            return;
+        }
+    };
+    let client = discord_next::rest_client::Client::new(vars.bot_token);
+    let (mut events, _handle) = conn.into_stream();
+    //events are handled one at a time, in the order they were received
+    while let Some(event) = events.next().await {
+        match event {
+            //always the first event, followed by a GuildCreate for each guild
+            Ok(discord_next::model::ReceivableEvent::Ready(ready)) => {
+                println!("connected as {}", ready.user.username);
+            }
+            Ok(discord_next::model::ReceivableEvent::MessageCreate(msg)) => {
+                if msg.content.starts_with(ACTIVATOR) {
+                    let cmd: String = msg.content[ACTIVATOR.len()..].trim().to_owned();
+                    let res = client
+                        .send_message(
+                            msg.channel_id,
+                            discord_next::rest_client::NewMessage::text(cmd),
+                        )
+                        .await;
+                    if let Err(e) = res {
+                        println!("couldn't echo: {}", e);
+                    }
+                }
+            }
+            Ok(_other) => {}
+            Err(e) => {
+                println!("Bot closed, res: {:?}", e);
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,458 @@
+use std::{
+    collections::{HashMap,VecDeque},
+    sync::{Arc,RwLock},
+};
+use bitflags::bitflags;
+use crate::model::{self,ChannelId,GuildId,MessageId,RoleId,UserId};
+
+use tracing::*;
+
+bitflags! {
+    ///The kinds of resource a `Cache` keeps track of
+    pub struct ResourceTypes: u32 {
+        const GUILDS    = (1 << 0);
+        const CHANNELS  = (1 << 1);
+        const MEMBERS   = (1 << 2);
+        const ROLES     = (1 << 3);
+        const PRESENCES = (1 << 4);
+        const MESSAGES  = (1 << 5);
+    }
+}
+
+impl Default for ResourceTypes{
+    fn default() -> Self{
+        Self::all()
+    }
+}
+
+#[derive(Debug,Clone)]
+pub struct CacheConfig{
+    ///which resources to cache, events for anything else are ignored
+    pub resource_types: ResourceTypes,
+    ///how many of the most recent messages to keep per channel
+    pub message_limit: usize,
+}
+
+impl Default for CacheConfig{
+    fn default() -> Self{
+        Self{
+            resource_types: Default::default(),
+            message_limit: 100,
+        }
+    }
+}
+
+#[derive(Default)]
+struct CacheInner{
+    ///the user the connection is logged in as
+    current_user: Option<model::User>,
+    ///guilds, with their channels, members and presences moved into the maps below
+    guilds: HashMap<GuildId,model::Guild>,
+    channels: HashMap<ChannelId,model::Channel>,
+    members: HashMap<GuildId,HashMap<UserId,model::GuildMember>>,
+    presences: HashMap<GuildId,HashMap<UserId,model::PresenceUpdate>>,
+    messages: HashMap<ChannelId,VecDeque<model::Message>>,
+}
+
+///An in-memory cache of guild state, kept up to date by passing it every event received from the gateway.
+///Clones share the same underlying storage.
+#[derive(Clone,Default)]
+pub struct Cache{
+    config: Arc<CacheConfig>,
+    inner: Arc<RwLock<CacheInner>>,
+}
+
+impl Cache{
+    pub fn new(config: CacheConfig) -> Self{
+        Self{
+            config: Arc::new(config),
+            inner: Default::default(),
+        }
+    }
+
+    pub fn config(&self) -> &CacheConfig{
+        &self.config
+    }
+
+    fn wants(&self, resource_type: ResourceTypes) -> bool{
+        self.config.resource_types.contains(resource_type)
+    }
+
+    ///Updates the cache from a gateway event
+    pub fn update(&self, event: &model::ReceivableEvent){
+        use model::ReceivableEvent::*;
+
+        let mut inner = self.inner.write().expect("Cache poisoned");
+        match event{
+            Ready(ready) => {
+                inner.current_user = Some(ready.user.clone());
+                if self.wants(ResourceTypes::CHANNELS){
+                    for channel in &ready.private_channels{
+                        inner.channels.insert(channel.id,channel.clone());
+                    }
+                }
+            }
+            UserUpdate(user) => {
+                if inner.current_user.as_ref().map(|current| current.id) == Some(user.id){
+                    inner.current_user = Some(user.clone());
+                }
+            }
+            GuildCreate(guild) => {
+                self.insert_guild(&mut inner,guild.clone());
+            }
+            GuildUpdate(guild) => {
+                if self.wants(ResourceTypes::GUILDS){
+                    //updates don't carry the fields only sent on GuildCreate, so keep the ones we have
+                    let mut guild = guild.clone();
+                    if let Some(existing) = inner.guilds.remove(&guild.id){
+                        guild.joined_at = guild.joined_at.or(existing.joined_at);
+                        guild.large = guild.large.or(existing.large);
+                        guild.member_count = guild.member_count.or(existing.member_count);
+                        guild.voice_states = guild.voice_states.or(existing.voice_states);
+                    }
+                    guild.channels = None;
+                    guild.members = None;
+                    guild.presences = None;
+                    if !self.wants(ResourceTypes::ROLES){
+                        guild.roles.clear();
+                    }
+                    inner.guilds.insert(guild.id,guild);
+                }
+            }
+            GuildDelete(unavailable_guild) => {
+                if unavailable_guild.unavailable{
+                    //outage, the guild will be sent again with a GuildCreate once it is available
+                    if let Some(guild) = inner.guilds.get_mut(&unavailable_guild.id){
+                        guild.unavailable = Some(true);
+                    }
+                }else{
+                    Self::remove_guild(&mut inner,unavailable_guild.id);
+                }
+            }
+            ChannelCreate(channel) | ChannelUpdate(channel) => {
+                if self.wants(ResourceTypes::CHANNELS){
+                    inner.channels.insert(channel.id,channel.clone());
+                }
+            }
+            ChannelDelete(channel) | ThreadDelete(channel) => {
+                inner.channels.remove(&channel.id);
+                inner.messages.remove(&channel.id);
+            }
+            ThreadCreate(thread) | ThreadUpdate(thread) => {
+                if self.wants(ResourceTypes::CHANNELS){
+                    inner.channels.insert(thread.id,thread.clone());
+                }
+            }
+            ThreadListSync(thread_list_sync) => {
+                if self.wants(ResourceTypes::CHANNELS){
+                    for thread in &thread_list_sync.threads{
+                        inner.channels.insert(thread.id,thread.clone());
+                    }
+                }
+            }
+            GuildMemberAdd(member_add) => {
+                if self.wants(ResourceTypes::MEMBERS){
+                    inner.members.entry(member_add.guild_id).or_default().insert(member_add.member.user.id,member_add.member.clone());
+                }
+            }
+            GuildMemberUpdate(member_update) => {
+                if let Some(member) = inner.members.get_mut(&member_update.guild_id).and_then(|members| members.get_mut(&member_update.user.id)){
+                    member.user = member_update.user.clone();
+                    member.roles = member_update.roles.clone();
+                    member.nick = member_update.nick.clone();
+                }
+            }
+            GuildMemberRemove(member_remove) => {
+                if let Some(members) = inner.members.get_mut(&member_remove.guild_id){
+                    members.remove(&member_remove.user.id);
+                }
+                if let Some(presences) = inner.presences.get_mut(&member_remove.guild_id){
+                    presences.remove(&member_remove.user.id);
+                }
+            }
+            GuildMembersChunk(chunk) => {
+                if self.wants(ResourceTypes::MEMBERS){
+                    let members = inner.members.entry(chunk.guild_id).or_default();
+                    for member in &chunk.members{
+                        members.insert(member.user.id,member.clone());
+                    }
+                }
+            }
+            GuildRoleCreate(model::GuildRoleCreate{guild_id,role}) | GuildRoleUpdate(model::GuildRoleUpdate{guild_id,role}) => {
+                if self.wants(ResourceTypes::ROLES){
+                    if let Some(guild) = inner.guilds.get_mut(guild_id){
+                        guild.roles.retain(|existing| existing.id != role.id);
+                        guild.roles.push(role.clone());
+                    }
+                }
+            }
+            GuildRoleDelete(role_delete) => {
+                if let Some(guild) = inner.guilds.get_mut(&role_delete.guild_id){
+                    guild.roles.retain(|existing| existing.id != role_delete.role_id);
+                }
+            }
+            PresenceUpdate(presence) => {
+                if self.wants(ResourceTypes::PRESENCES){
+                    if let Some(guild_id) = presence.guild_id{
+                        inner.presences.entry(guild_id).or_default().insert(presence.user.id,presence.clone());
+                    }
+                }
+            }
+            MessageCreate(message) => {
+                if self.wants(ResourceTypes::MESSAGES) && self.config.message_limit > 0{
+                    let messages = inner.messages.entry(message.channel_id).or_default();
+                    while messages.len() >= self.config.message_limit{
+                        messages.pop_front();
+                    }
+                    messages.push_back(message.clone());
+                }
+            }
+            MessageDelete(message_delete) => {
+                if let Some(messages) = inner.messages.get_mut(&message_delete.channel_id){
+                    messages.retain(|message| message.id != message_delete.id);
+                }
+            }
+            MessageDeleteBulk(message_delete_bulk) => {
+                if let Some(messages) = inner.messages.get_mut(&message_delete_bulk.channel_id){
+                    messages.retain(|message| !message_delete_bulk.ids.contains(&message.id));
+                }
+            }
+            _other => {}
+        }
+    }
+
+    fn insert_guild(&self, inner: &mut CacheInner, mut guild: model::Guild){
+        trace!("caching guild {:?}",guild.id);
+        let guild_id = guild.id;
+        if let Some(channels) = guild.channels.take(){
+            if self.wants(ResourceTypes::CHANNELS){
+                for mut channel in channels{
+                    //channels sent as part of a guild don't include the guild id
+                    channel.guild_id = Some(guild_id);
+                    inner.channels.insert(channel.id,channel);
+                }
+            }
+        }
+        if let Some(members) = guild.members.take(){
+            if self.wants(ResourceTypes::MEMBERS){
+                inner.members.entry(guild_id).or_default().extend(members.into_iter().map(|member| (member.user.id,member)));
+            }
+        }
+        if let Some(presences) = guild.presences.take(){
+            if self.wants(ResourceTypes::PRESENCES){
+                inner.presences.entry(guild_id).or_default().extend(presences.into_iter().map(|presence| (presence.user.id,presence)));
+            }
+        }
+        if !self.wants(ResourceTypes::ROLES){
+            guild.roles.clear();
+        }
+        if self.wants(ResourceTypes::GUILDS){
+            inner.guilds.insert(guild_id,guild);
+        }
+    }
+
+    fn remove_guild(inner: &mut CacheInner, guild_id: GuildId){
+        trace!("removing guild {:?} from cache",guild_id);
+        inner.guilds.remove(&guild_id);
+        inner.members.remove(&guild_id);
+        inner.presences.remove(&guild_id);
+        let channel_ids: Vec<ChannelId> = inner.channels.values()
+            .filter(|channel| channel.guild_id == Some(guild_id))
+            .map(|channel| channel.id)
+            .collect();
+        for channel_id in channel_ids{
+            inner.channels.remove(&channel_id);
+            inner.messages.remove(&channel_id);
+        }
+    }
+
+    ///The user the connection is logged in as, once the Ready event has been seen
+    pub fn current_user(&self) -> Option<model::User>{
+        self.inner.read().expect("Cache poisoned").current_user.clone()
+    }
+
+    pub fn guild(&self, guild_id: GuildId) -> Option<model::Guild>{
+        self.inner.read().expect("Cache poisoned").guilds.get(&guild_id).cloned()
+    }
+
+    pub fn guild_ids(&self) -> Vec<GuildId>{
+        self.inner.read().expect("Cache poisoned").guilds.keys().cloned().collect()
+    }
+
+    pub fn channel(&self, channel_id: ChannelId) -> Option<model::Channel>{
+        self.inner.read().expect("Cache poisoned").channels.get(&channel_id).cloned()
+    }
+
+    pub fn guild_channels(&self, guild_id: GuildId) -> Vec<model::Channel>{
+        self.inner.read().expect("Cache poisoned").channels.values()
+            .filter(|channel| channel.guild_id == Some(guild_id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn member(&self, guild_id: GuildId, user_id: UserId) -> Option<model::GuildMember>{
+        self.inner.read().expect("Cache poisoned").members.get(&guild_id).and_then(|members| members.get(&user_id)).cloned()
+    }
+
+    pub fn members(&self, guild_id: GuildId) -> Vec<model::GuildMember>{
+        self.inner.read().expect("Cache poisoned").members.get(&guild_id).map(|members| members.values().cloned().collect()).unwrap_or_default()
+    }
+
+    pub fn role(&self, guild_id: GuildId, role_id: RoleId) -> Option<model::Role>{
+        self.inner.read().expect("Cache poisoned").guilds.get(&guild_id).and_then(|guild| guild.roles.iter().find(|role| role.id == role_id)).cloned()
+    }
+
+    pub fn presence(&self, guild_id: GuildId, user_id: UserId) -> Option<model::PresenceUpdate>{
+        self.inner.read().expect("Cache poisoned").presences.get(&guild_id).and_then(|presences| presences.get(&user_id)).cloned()
+    }
+
+    pub fn message(&self, channel_id: ChannelId, message_id: MessageId) -> Option<model::Message>{
+        self.inner.read().expect("Cache poisoned").messages.get(&channel_id).and_then(|messages| messages.iter().find(|message| message.id == message_id)).cloned()
+    }
+
+    ///The most recent messages in a channel, oldest first
+    pub fn messages(&self, channel_id: ChannelId) -> Vec<model::Message>{
+        self.inner.read().expect("Cache poisoned").messages.get(&channel_id).map(|messages| messages.iter().cloned().collect()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test{
+    use super::*;
+
+    fn message_create(id: u64) -> model::ReceivableEvent{
+        model::ReceivableEvent::MessageCreate(serde_json::from_value(serde_json::json!({
+            "id": id.to_string(),
+            "channel_id": "1",
+            "author": {"id": "2", "username": "test", "discriminator": "0001"},
+            "content": "",
+            "timestamp": "2021-01-01T00:00:00+00:00",
+            "edited_timestamp": null,
+            "tts": false,
+            "mention_everyone": false,
+            "mentions": [],
+            "mention_roles": [],
+            "attachments": [],
+            "embeds": [],
+            "pinned": false,
+            "type": 0,
+        })).unwrap())
+    }
+
+    fn event(name: &str, data: serde_json::Value) -> model::ReceivableEvent{
+        let payload: model::Payload = serde_json::from_value(serde_json::json!({"op": 0, "s": 1, "t": name, "d": data})).unwrap();
+        match std::convert::TryInto::<model::GatewayEvent>::try_into(payload).unwrap(){
+            model::GatewayEvent::ReceivableEvent(event) => event,
+            other => panic!("not a dispatch: {:?}",other),
+        }
+    }
+
+    fn user(id: &str) -> serde_json::Value{
+        serde_json::json!({"id": id, "username": "test", "discriminator": "0001"})
+    }
+
+    fn guild_create() -> model::ReceivableEvent{
+        event("GUILD_CREATE",serde_json::json!({
+            "id": "100",
+            "name": "guild",
+            "icon": null,
+            "splash": null,
+            "owner_id": "2",
+            "region": "europe",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "verification_level": 0,
+            "default_message_notifications": 0,
+            "explicit_content_filter": 0,
+            "roles": [],
+            "emojis": [],
+            "features": [],
+            "mfa_level": 0,
+            "application_id": null,
+            "system_channel_id": null,
+            "channels": [{"id": "101", "type": 0, "name": "general"}],
+            "members": [{"user": user("2"), "roles": [], "joined_at": "2021-01-01T00:00:00+00:00", "deaf": false, "mute": false}],
+        }))
+    }
+
+    #[test]
+    fn ready_sets_current_user_and_private_channels(){
+        let cache = Cache::default();
+        cache.update(&event("READY",serde_json::json!({
+            "v": 9,
+            "user": user("2"),
+            "private_channels": [{"id": "5", "type": 1}],
+            "guilds": [],
+            "session_id": "session",
+            "_trace": [],
+        })));
+        assert_eq!(cache.current_user().map(|user| user.id),Some(UserId(model::Snowflake(2))));
+        assert!(cache.channel(ChannelId(model::Snowflake(5))).is_some());
+    }
+
+    #[test]
+    fn guild_create_and_delete(){
+        let cache = Cache::default();
+        let guild_id = GuildId(model::Snowflake(100));
+        cache.update(&guild_create());
+        assert!(cache.guild(guild_id).is_some());
+        //channels and members are moved out of the guild into their own maps
+        let channels = cache.guild_channels(guild_id);
+        assert_eq!(channels.len(),1);
+        assert_eq!(channels[0].guild_id,Some(guild_id));
+        assert_eq!(cache.members(guild_id).len(),1);
+
+        cache.update(&event("GUILD_DELETE",serde_json::json!({"id": "100", "unavailable": true})));
+        assert_eq!(cache.guild(guild_id).and_then(|guild| guild.unavailable),Some(true));
+
+        cache.update(&event("GUILD_DELETE",serde_json::json!({"id": "100", "unavailable": false})));
+        assert!(cache.guild(guild_id).is_none());
+        assert!(cache.channel(ChannelId(model::Snowflake(101))).is_none());
+        assert!(cache.members(guild_id).is_empty());
+    }
+
+    #[test]
+    fn member_add_and_remove(){
+        let cache = Cache::default();
+        let guild_id = GuildId(model::Snowflake(100));
+        let user_id = UserId(model::Snowflake(3));
+        cache.update(&event("GUILD_MEMBER_ADD",serde_json::json!({
+            "guild_id": "100",
+            "user": user("3"),
+            "roles": [],
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+        })));
+        assert!(cache.member(guild_id,user_id).is_some());
+
+        cache.update(&event("GUILD_MEMBER_REMOVE",serde_json::json!({"guild_id": "100", "user": user("3")})));
+        assert!(cache.member(guild_id,user_id).is_none());
+    }
+
+    #[test]
+    fn unwanted_resources_are_skipped(){
+        let cache = Cache::new(CacheConfig{
+            resource_types: ResourceTypes::GUILDS,
+            ..Default::default()
+        });
+        let guild_id = GuildId(model::Snowflake(100));
+        cache.update(&guild_create());
+        assert!(cache.guild(guild_id).is_some());
+        assert!(cache.guild_channels(guild_id).is_empty());
+        assert!(cache.members(guild_id).is_empty());
+    }
+
+    #[test]
+    fn message_buffer_is_bounded(){
+        let cache = Cache::new(CacheConfig{
+            message_limit: 2,
+            ..Default::default()
+        });
+        for id in 10..13{
+            cache.update(&message_create(id));
+        }
+        let ids: Vec<u64> = cache.messages(ChannelId(model::Snowflake(1))).iter().map(|message| (message.id.0).0).collect();
+        assert_eq!(ids,vec![11,12]);
+    }
+}
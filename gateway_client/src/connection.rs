@@ -4,16 +4,19 @@ use std::{
     convert::{TryFrom,TryInto},
     collections::HashMap,
     sync::{Arc},
+    time::{Duration,Instant},
 };
 use futures::{
     prelude::*,
     sink::{Sink},
     stream,
-    channel::mpsc::UnboundedSender,
+    channel::mpsc::{UnboundedSender,UnboundedReceiver,unbounded},
 };
+use rand::Rng as _;
+use url::Url;
 use crate::{
-    extensions::*,
     close_on_drop::CloseOnDrop,
+    handler::{Context,EventHandler},
     Error,
     model::{self,UserId},
 };
@@ -34,18 +37,153 @@ pub (crate) struct ConnectionWriter{
     pub sink: UnboundedSender<model::GatewayCommand>,
 }
 
+///A stream of events from a gateway connection, see `Connection::into_stream`
+pub type EventStream = Pin<Box<dyn Stream<Item=Result<model::ReceivableEvent,Error>> + Send + 'static>>;
+
+///A cloneable handle to a gateway connection, used to send commands on it
+#[derive(Clone)]
+pub struct ConnectionHandle{
+    writer: ConnectionWriter,
+    pub user: model::User,
+    pub session_id: String,
+    #[cfg(feature="voice")]
+    pub (crate) voice_update_store: VoiceStateStore,
+    #[cfg(feature="voice")]
+    pub (crate) voice_state: Arc<Mutex<HashMap<UserId,model::VoiceState>>>,
+}
+
+impl ConnectionHandle{
+    #[cfg(feature="voice")]
+    pub (crate) fn clone_writer(&self) -> ConnectionWriter{
+        self.writer.clone()
+    }
+
+    ///Queues a command to be sent to the gateway
+    pub fn send<C: Into<model::GatewayCommand>>(&self, command: C) -> Result<(),Error>{
+        self.writer.sink.unbounded_send(command.into()).map_err(|e| e.into_send_error())?;
+        Ok(())
+    }
+}
+
+enum Step{
+    Event(model::ReceivableEvent),
+    Continue,
+    Complete,
+}
+
+type GatewaySink = CloseOnDrop<Box<dyn Sink<model::GatewayCommand,Error=Error>+Send+Unpin>,model::GatewayCommand>;
+type GatewayStream = Pin<Box<dyn Stream<Item=Result<model::Payload,Error>> + Send + 'static>>;
+///a freshly opened websocket and the hello packet it was greeted with
+type OpenGateway = (GatewaySink,GatewayStream,model::Hello);
+
+///opens a websocket to the gateway and waits for the hello packet
+async fn open_gateway(url: &Url) -> Result<OpenGateway,Error>{
+    let (stream,_res) = tokio_tungstenite::connect_async(url.clone()).await?;
+    let (sink,stream) = stream.split();
+    let sink: Box<dyn Sink<model::GatewayCommand,Error=Error>+Send+Unpin> = Box::new(sink.sink_map_err(Error::from).with(|payload: model::GatewayCommand|{
+        future::lazy(|_|{
+            let payload = model::Payload::try_from_command(payload)?;
+            let payload = serde_json::to_string(&payload)?;
+            trace!("sending payload: {:?}",payload);
+            Ok(tungstenite::Message::Text(payload))
+        })
+    }));
+    let mut stream: GatewayStream = Box::pin(stream.map_err(Error::from).and_then(|message|{
+        future::lazy(|_|{
+            if let tungstenite::Message::Close(close_frame) = message {
+                return Err(Error::ConnectionClosed(close_frame.and_then(|frame| model::CloseCode::try_from(Into::<u16>::into(frame.code)).ok())));
+            }
+            let text = message.into_text()?;
+            trace!("Parsing: {}",&text);
+            let payload: model::Payload = serde_json::from_str(text.as_str())?;
+            Ok(payload)
+        })
+    }));
+
+    let event: model::GatewayEvent = stream.try_next().await?.ok_or(Error::ConnectionClosed(None))?.try_into()?;
+
+    debug!("packet, should be hello: {:#?}",event);
+    let hello = event.expect_hello();
+    trace!("{:#?}",hello);
+    Ok((CloseOnDrop::new(sink),stream,hello))
+}
+
+///how many times reconnecting is tried before the connection gives up
+const RECONNECT_ATTEMPTS: u32 = 10;
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+///how long to wait after `attempt` failed reconnects, doubling each time up to a limit, with some jitter so shards don't reconnect in lockstep
+fn reconnect_delay(attempt: u32) -> Duration{
+    let backoff = RECONNECT_BASE_DELAY.checked_mul(1 << attempt.min(16)).unwrap_or(RECONNECT_MAX_DELAY).min(RECONNECT_MAX_DELAY);
+    backoff + Duration::from_millis(rand::thread_rng().gen_range(0,500))
+}
+
+fn heartbeat_timer(heartbeat_interval: u64) -> stream::Fuse<tokio::time::Interval>{
+    tokio::time::interval(tokio::time::Duration::from_millis(heartbeat_interval)).fuse()
+}
+
+///Forwards commands to whichever websocket is currently attached, so that writers handed out before a reconnect stay valid.
+///Commands sent while no websocket is attached are held until one is.
+fn spawn_command_relay(mut commands: UnboundedReceiver<model::GatewayCommand>, mut sinks: UnboundedReceiver<Option<GatewaySink>>){
+    use futures::select;
+
+    tokio::spawn(async move{
+        let mut current: Option<GatewaySink> = None;
+        loop{
+            let attached = match current.as_mut(){
+                Some(attached) => attached,
+                None => {
+                    match sinks.next().await{
+                        Some(sink) => current = sink,
+                        None => return,
+                    }
+                    continue;
+                }
+            };
+            select!{
+                sink = sinks.next() => match sink{
+                    Some(sink) => current = sink,
+                    None => return,
+                },
+                command = commands.next() => match command{
+                    Some(command) => {
+                        if let Err(e) = attached.send(command).await{
+                            warn!("Error in gateway command relay: {:?}",e);
+                        }
+                    }
+                    None => return,
+                },
+            }
+        }
+    });
+}
+
 pub struct Connection{
     pub session_id: String,
-    stream: stream::Fuse<Pin<Box<dyn Stream<Item=Result<model::Payload,Error>> + Send + 'static>>>,
+    stream: stream::Fuse<GatewayStream>,
     heartbeat_timer: stream::Fuse<tokio::time::Interval>,
+    //whether the last heartbeat we sent has been acknowledged
+    heartbeat_acked: bool,
+    //when the last heartbeat was sent, used to measure latency
+    last_heartbeat: Option<Instant>,
+    latency: Option<Duration>,
     sink: UnboundedSender<model::GatewayCommand>,
+    //used to swap out the websocket the commands in `sink` are forwarded to
+    sink_swap: UnboundedSender<Option<GatewaySink>>,
+    gateway_url: Url,
     token: String,
+    shard: Option<[u64;2]>,
     seq_num: Option<u64>,
+    //the READY received while connecting, handed out as the first event
+    initial_ready: Option<model::Ready>,
     #[cfg(feature="voice")]
     voice_update_store: VoiceStateStore,
     pub user: model::User,
     #[cfg(feature="voice")]
     pub voice_state: Arc<Mutex<HashMap<UserId,model::VoiceState>>>,
+    #[cfg(feature="cache")]
+    cache: Option<crate::cache::Cache>,
 }
 
 impl Connection{
@@ -57,8 +195,94 @@ impl Connection{
     }
 
     fn update_seq_num(&mut self, new_seq_num: Option<u64>){
+        //only dispatches carry a sequence number, so keep the last one we saw for everything else
         //TODO: should we only count upwards?
-        self.seq_num = new_seq_num;
+        if new_seq_num.is_some(){
+            self.seq_num = new_seq_num;
+        }
+    }
+
+    fn attach_sink(&self, sink: Option<GatewaySink>) -> Result<(),Error>{
+        self.sink_swap.unbounded_send(sink).map_err(|e| e.into_send_error())?;
+        Ok(())
+    }
+
+    ///Re-establishes the gateway connection, resuming the current session if `resume` is set and we have seen a sequence number, otherwise identifying as a new session.
+    ///The handler is sent `Resumed` or `Ready` respectively once the gateway accepts the connection.
+    ///Failed attempts are retried with exponential backoff, the error is only returned once `RECONNECT_ATTEMPTS` have failed.
+    async fn reconnect(&mut self, resume: bool) -> Result<(),Error>{
+        //detach the old websocket first so nothing else gets written to it
+        self.attach_sink(None)?;
+        let mut attempt = 0;
+        loop{
+            match self.try_reconnect(resume).await{
+                Ok(()) => return Ok(()),
+                Err(e) if attempt + 1 < RECONNECT_ATTEMPTS => {
+                    let delay = reconnect_delay(attempt);
+                    warn!("reconnect attempt {} failed ({:?}), retrying in {:?}",attempt + 1,e,delay);
+                    tokio::time::delay_for(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn try_reconnect(&mut self, resume: bool) -> Result<(),Error>{
+        let (mut sink,stream,hello) = open_gateway(&self.gateway_url).await?;
+        match (resume,self.seq_num){
+            (true,Some(seq)) => {
+                debug!("resuming session {} at seq {}",self.session_id,seq);
+                sink.send(model::Resume{
+                    token: self.token.clone(),
+                    session_id: self.session_id.clone(),
+                    seq,
+                }.into()).await?;
+            }
+            _ => {
+                debug!("identifying new session");
+                self.seq_num = None;
+                sink.send(self.identify().into()).await?;
+            }
+        }
+        self.stream = stream.fuse();
+        self.heartbeat_timer = heartbeat_timer(hello.heartbeat_interval);
+        self.heartbeat_acked = true;
+        self.last_heartbeat = None;
+        self.attach_sink(Some(sink))
+    }
+
+    fn identify(&self) -> model::Identify{
+        let mut identify = model::Identify::new(self.token.clone());
+        identify.shard = self.shard;
+        identify
+    }
+
+    ///The `[shard_id, num_shards]` pair this connection identified with, if it is sharded
+    pub fn shard(&self) -> Option<[u64;2]>{
+        self.shard
+    }
+
+    async fn send_heartbeat(&mut self) -> Result<(),Error>{
+        self.sink.send(crate::model::Heartbeat{last_seq: self.seq_num}.into()).await?;
+        self.last_heartbeat = Some(Instant::now());
+        Ok(())
+    }
+
+    ///The round trip time of the most recently acknowledged heartbeat, if any have been acknowledged yet on this connection
+    pub fn latency(&self) -> Option<Duration>{
+        self.latency
+    }
+
+    ///Sets a cache to be updated with every event, before it is passed to the event handler
+    #[cfg(feature="cache")]
+    pub fn set_cache(&mut self, cache: crate::cache::Cache){
+        self.cache = Some(cache);
+    }
+
+    #[cfg(feature="cache")]
+    pub fn cache(&self) -> Option<&crate::cache::Cache>{
+        self.cache.as_ref()
     }
 
     #[cfg(feature="voice")]
@@ -74,11 +298,20 @@ impl Connection{
         }).await
     }
 
-    async fn handle_dispatch<E,F,Fut>(&mut self, event: model::ReceivableEvent, client: &crate::rest_client::Client, f: &mut F) -> Result<(),Error>
-        where F: FnMut(&mut Self, model::ReceivableEvent,crate::rest_client::Client) -> Fut,
-            Fut: std::future::Future<Output = Result<(),E>> + Send + 'static,
-            E: std::fmt::Debug + From<Error>
-    {
+    ///updates connection state from a dispatched event, returning the event if it should be passed on to the user
+    async fn preprocess_dispatch(&mut self, event: model::ReceivableEvent) -> Option<model::ReceivableEvent>{
+        if let model::ReceivableEvent::Ready(ref ready) = event{
+            //either the initial session, or we re-identified after a reconnect, so any state tied to the old session is gone
+            debug!("new session: {}",ready.session_id);
+            self.session_id = ready.session_id.clone();
+            self.user = ready.user.clone();
+        }
+        #[cfg(feature="cache")]
+        {
+            if let Some(cache) = self.cache.as_ref(){
+                cache.update(&event);
+            }
+        }
         match event{
             #[cfg(feature="voice")]
             model::ReceivableEvent::VoiceServerUpdate(voice_server_update) => {
@@ -91,42 +324,114 @@ impl Connection{
                         warn!("Send error when updating voice state.")
                     }
                 }
+                None
             },
             #[cfg(feature="voice")]
             model::ReceivableEvent::VoiceStateUpdate(voice_state) => {
                 info!("got voice state for user {:?}", voice_state.user_id);
                 self.voice_state.lock().await.insert(voice_state.user_id,voice_state);
                 //TODO: *also* pass this event to the event handler
+                None
             }
-            other => {
-                let fut = f(self, other,client.clone());
+            other => Some(other),
+        }
+    }
+
+    ///returns true if complete
+    pub async fn turn<E,F,Fut>(&mut self, client: &crate::rest_client::Client, f: &mut F) -> Result<bool,Error>
+        where F: FnMut(&mut Self, model::ReceivableEvent,crate::rest_client::Client) -> Fut,
+            Fut: std::future::Future<Output = Result<(),E>> + Send + 'static,
+            E: std::fmt::Debug + From<Error>
+    {
+        match self.step().await?{
+            Step::Event(event) => {
+                let fut = f(self, event,client.clone());
                 tokio::spawn(async{
                     if let Err(ref e) = fut.await{
                         //warn on errors, but expect them to be recoverable, so don't abort
                         warn!("event handler error {:?}",e)
                     }
                 });
+                Ok(false)
             }
+            Step::Continue => Ok(false),
+            Step::Complete => Ok(true),
         }
-        Ok(())
     }
 
-    ///returns true if complete
-    pub async fn turn<E,F,Fut>(&mut self, client: &crate::rest_client::Client, f: &mut F) -> Result<bool,Error>
-        where F: FnMut(&mut Self, model::ReceivableEvent,crate::rest_client::Client) -> Fut,
-            Fut: std::future::Future<Output = Result<(),E>> + Send + 'static,
-            E: std::fmt::Debug + From<Error>
-    {
+    ///Waits for the next event from the gateway, handling heartbeats and reconnects along the way.
+    ///Returns `None` once the connection is complete.
+    pub async fn next_event(&mut self) -> Option<Result<model::ReceivableEvent,Error>>{
+        loop{
+            match self.step().await{
+                Ok(Step::Event(event)) => return Some(Ok(event)),
+                Ok(Step::Continue) => {},
+                Ok(Step::Complete) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    ///A cloneable handle for sending commands on this connection, which stays valid across reconnects
+    pub fn handle(&self) -> ConnectionHandle{
+        ConnectionHandle{
+            writer: self.clone_writer(),
+            user: self.user.clone(),
+            session_id: self.session_id.clone(),
+            #[cfg(feature="voice")]
+            voice_update_store: self.voice_update_store.clone(),
+            #[cfg(feature="voice")]
+            voice_state: self.voice_state.clone(),
+        }
+    }
+
+    ///Turns the connection into a stream of events, and a handle for sending commands on it
+    pub fn into_stream(self) -> (EventStream,ConnectionHandle){
+        let handle = self.handle();
+        let events = stream::unfold(self,|mut conn| async move{
+            conn.next_event().await.map(|event| (event,conn))
+        });
+        (Box::pin(events),handle)
+    }
+
+    async fn step(&mut self) -> Result<Step,Error>{
         use futures::select;
 
+        if let Some(ready) = self.initial_ready.take(){
+            if let Some(event) = self.preprocess_dispatch(model::ReceivableEvent::Ready(ready)).await{
+                return Ok(Step::Event(event));
+            }
+        }
+
         select!{
             _beat = self.heartbeat_timer.next() => {
-                self.sink.send(crate::model::Heartbeat{last_seq: None}.into()).await?;
-                return Ok(false);
+                if !self.heartbeat_acked{
+                    //the gateway stopped responding without closing the connection, so it's probably a zombie
+                    warn!("no heartbeat ack since last heartbeat, reconnecting");
+                    self.reconnect(true).await?;
+                    return Ok(Step::Continue);
+                }
+                self.heartbeat_acked = false;
+                self.send_heartbeat().await?;
+                return Ok(Step::Continue);
             },
             payload = self.stream.next() => {
                 let payload = match payload{
-                    None => return Ok(false),
+                    None => {
+                        warn!("gateway stream ended, reconnecting");
+                        self.reconnect(true).await?;
+                        return Ok(Step::Continue);
+                    }
+                    Some(Err(Error::ConnectionClosed(close_code))) if close_code.as_ref().map(model::CloseCode::can_reconnect).unwrap_or(true) => {
+                        warn!("gateway connection closed ({:?}), reconnecting",close_code);
+                        self.reconnect(close_code.as_ref().map(model::CloseCode::can_resume).unwrap_or(true)).await?;
+                        return Ok(Step::Continue);
+                    }
+                    Some(Err(Error::Ws(e))) => {
+                        warn!("gateway websocket error ({:?}), reconnecting",e);
+                        self.reconnect(true).await?;
+                        return Ok(Step::Continue);
+                    }
                     Some(Err(e)) => return Err(e),
                     Some(Ok(payload)) => payload,
                 };
@@ -135,7 +440,7 @@ impl Connection{
                     Ok(o) => o,
                     Err(model::FromPayloadError::UnknownOpcode(op)) => {
                         warn!("Unknown voice opcode {}", op);
-                        return Ok(false);
+                        return Ok(Step::Continue);
                     },
                     Err(other) => {
                         return Err(other.into());
@@ -144,32 +449,63 @@ impl Connection{
                 trace!("got gateway_event: {:?}",gateway_event);
                 match gateway_event{
                     model::GatewayEvent::HeartbeatAck => {
-                        //don't really care
+                        self.heartbeat_acked = true;
+                        if let Some(sent) = self.last_heartbeat{
+                            self.latency = Some(sent.elapsed());
+                        }
                     }
                     model::GatewayEvent::HeartbeatRequest => {
-                        self.sink.send(crate::model::Heartbeat{last_seq: None}.into()).await?;
+                        self.send_heartbeat().await?;
                     }
                     model::GatewayEvent::Hello(hello) => {
                         warn!("unexpected hello payload: {:?}",hello);
                         //most resilient thing to do here is just continue probably
                     }
                     model::GatewayEvent::ReceivableEvent(event) => {
-                        self.handle_dispatch(event,client,f).await?;
+                        if let Some(event) = self.preprocess_dispatch(event).await{
+                            return Ok(Step::Event(event));
+                        }
                     }
                     model::GatewayEvent::Reconnect => {
-                        todo!("implement gateway reconnect")
+                        debug!("gateway requested reconnect");
+                        self.reconnect(true).await?;
                     }
-                    model::GatewayEvent::InvalidSession(_) => {
-                        todo!("implement gateway invalid session")
+                    model::GatewayEvent::InvalidSession(invalid_session) => {
+                        if invalid_session.resumable{
+                            debug!("invalid session, resuming");
+                            self.reconnect(true).await?;
+                        }else{
+                            //discord asks for a random wait of 1-5 seconds before identifying again
+                            let wait = rand::thread_rng().gen_range(1000,5001);
+                            debug!("invalid session, identifying again in {}ms",wait);
+                            tokio::time::delay_for(tokio::time::Duration::from_millis(wait)).await;
+                            self.reconnect(false).await?;
+                        }
                     }
                 }
-                return Ok(false);
+                return Ok(Step::Continue);
             },
-            complete => return Ok(true),
-            default => return Ok(false),
+            complete => return Ok(Step::Complete),
         }
     }
 
+    ///Runs the connection to completion, passing every event to `handler`
+    pub async fn run_handler<H: EventHandler + 'static>(self, handler: H) -> Result<(),Error>{
+        self.run_handlers(vec![Arc::new(handler)]).await
+    }
+
+    ///Runs the connection to completion, passing every event to each of `handlers` in order
+    pub async fn run_handlers(self, handlers: Vec<Arc<dyn EventHandler>>) -> Result<(),Error>{
+        let handlers = Arc::new(handlers);
+        self.run(move |conn, event, client|{
+            let ctx = Context{
+                client,
+                connection: conn.handle(),
+            };
+            crate::handler::dispatch_all(handlers.clone(),ctx,event).map(Result::<(),Error>::Ok)
+        }).await
+    }
+
     //runs Connection::turn to completion
     pub async fn run<E,F,Fut>(mut self, mut f: F) -> Result<(),Error>
         where F: FnMut(&mut Self, model::ReceivableEvent,crate::rest_client::Client) -> Fut,
@@ -184,60 +520,126 @@ impl Connection{
     pub async fn connect<S: Into<String>>(token: S) -> Result<Self,Error>{
         let token = token.into();
         let client = crate::rest_client::Client::new(token.clone());
-        let url = client.get_gateway(crate::GATEWAY_VERSION).await?;
-        let (stream,_res) = tokio_tungstenite::connect_async(url).await?;
-        let (sink,stream) = stream.split();
-        let mut sink: Box<dyn Sink<model::GatewayCommand,Error=Error>+Send+Unpin> = Box::new(sink.sink_map_err(Error::from).with(|payload: model::GatewayCommand|{
-            future::lazy(|_|{
-                let payload = model::Payload::try_from_command(payload)?;
-                let payload = serde_json::to_string(&payload)?;
-                trace!("sending payload: {:?}",payload);
-                Ok(tungstenite::Message::Text(payload))
-            })
-        }));
-        let mut stream = Box::pin(stream.map_err(Error::from).and_then(|message|{
-            future::lazy(|_|{
-                if let tungstenite::Message::Close(close_frame) = message {
-                    return Err(Error::ConnectionClosed(close_frame.and_then(|frame| model::CloseCode::try_from(Into::<u16>::into(frame.code)).ok())));
-                }
-                let text = message.into_text()?;
-                trace!("Parsing: {}",&text);
-                let payload: model::Payload = serde_json::from_str(text.as_str())?;
-                Ok(payload)
-            })
-        }));
-
-        let event: model::GatewayEvent = stream.try_next().await?.unwrap().try_into()?;
-
-        debug!("packet, should be hello: {:#?}",event);
-        let hello = event.expect_hello();
-        let heartbeat_interval = hello.heartbeat_interval;
-        trace!("{:#?}",hello);
-        let identify = model::Identify::new(token.clone());
+        let gateway_url = client.get_gateway(crate::GATEWAY_VERSION).await?;
+        Self::connect_to(token,gateway_url,None).await
+    }
+
+    pub (crate) async fn connect_to(token: String, gateway_url: Url, shard: Option<[u64;2]>) -> Result<Self,Error>{
+        let (mut sink,mut stream,hello) = open_gateway(&gateway_url).await?;
+
+        let mut identify = model::Identify::new(token.clone());
+        identify.shard = shard;
         debug!("sending identify payload: {:#?}",identify);
 
         sink.send(identify.into()).await?;
 
-        let sink = CloseOnDrop::new(sink);
-
-        let event: model::GatewayEvent = stream.try_next().await?.unwrap().try_into()?;
+        let payload = stream.try_next().await?.ok_or(Error::ConnectionClosed(None))?;
+        let seq_num = payload.s;
+        let event: model::GatewayEvent = payload.try_into()?;
 
         debug!("packet, should be event ready: {:?}",event);
         let ready = event.expect_event().expect_ready();
         trace!("{:#?}",ready);
 
+        Self::from_session(token,gateway_url,shard,(sink,stream,hello),ready,seq_num)
+    }
+
+    ///Sets up a connection on a gateway session that has already been identified, `ready` is returned as the first event
+    fn from_session(token: String, gateway_url: Url, shard: Option<[u64;2]>, gateway: OpenGateway, ready: model::Ready, seq_num: Option<u64>) -> Result<Self,Error>{
+        let (sink,stream,hello) = gateway;
+        let (command_sink,commands) = unbounded();
+        let (sink_swap,sinks) = unbounded();
+        spawn_command_relay(commands,sinks);
+        sink_swap.unbounded_send(Some(sink)).map_err(|e| e.into_send_error())?;
+
         Ok(Self{
-            session_id: ready.session_id,
-            sink: sink.unbounded_channeled(),
+            session_id: ready.session_id.clone(),
+            sink: command_sink,
+            sink_swap,
+            gateway_url,
             token,
-            user: ready.user,
-            stream: (stream as Pin<Box<dyn Stream<Item=Result<model::Payload,Error>> + Send + 'static>>).fuse(),
-            heartbeat_timer: tokio::time::interval(tokio::time::Duration::from_millis(heartbeat_interval)).fuse(),
-            seq_num: None,
+            shard,
+            user: ready.user.clone(),
+            stream: stream.fuse(),
+            heartbeat_timer: heartbeat_timer(hello.heartbeat_interval),
+            heartbeat_acked: true,
+            last_heartbeat: None,
+            latency: None,
+            seq_num,
+            initial_ready: Some(ready),
             #[cfg(feature="voice")]
             voice_update_store: Default::default(),
             #[cfg(feature="voice")]
             voice_state: Default::default(),
+            #[cfg(feature="cache")]
+            cache: None,
         })
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test{
+    use super::*;
+    use serde_json::json;
+
+    fn ready() -> model::Ready{
+        serde_json::from_value(json!({
+            "v": 9,
+            "user": {"id": "2", "username": "bot", "discriminator": "0001"},
+            "private_channels": [],
+            "guilds": [],
+            "session_id": "session",
+            "_trace": [],
+        })).unwrap()
+    }
+
+    fn dispatch(seq: u64, name: &str, data: serde_json::Value) -> Result<model::Payload,Error>{
+        Ok(serde_json::from_value(json!({"op": 0, "s": seq, "t": name, "d": data})).unwrap())
+    }
+
+    ///a connection reading `payloads`, with commands going nowhere
+    fn connection(payloads: Vec<Result<model::Payload,Error>>) -> Connection{
+        let sink: Box<dyn Sink<model::GatewayCommand,Error=Error>+Send+Unpin> = Box::new(futures::sink::drain().sink_map_err(|_| Error::ConnectionClosed(None)));
+        //never ends, so the connection doesn't try to reconnect
+        let stream: GatewayStream = Box::pin(stream::iter(payloads).chain(stream::pending()));
+        let hello = model::Hello{heartbeat_interval: 45000, _trace: vec![]};
+        Connection::from_session("token".into(),Url::parse("wss://gateway.invalid").unwrap(),None,(CloseOnDrop::new(sink),stream,hello),ready(),Some(1)).unwrap()
+    }
+
+    #[test]
+    fn reconnect_delay_backs_off_up_to_a_limit(){
+        let delays: Vec<Duration> = (0..10).map(reconnect_delay).collect();
+        assert!(delays[0] >= Duration::from_secs(1) && delays[0] < Duration::from_secs(2));
+        assert!(delays[3] >= Duration::from_secs(8) && delays[3] < Duration::from_secs(9));
+        for delay in &delays[6..]{
+            assert!(*delay >= RECONNECT_MAX_DELAY && *delay < RECONNECT_MAX_DELAY + Duration::from_secs(1));
+        }
+    }
+
+    #[tokio::test]
+    async fn initial_ready_is_the_first_event(){
+        let conn = connection(vec![
+            dispatch(2,"TYPING_START",json!({"channel_id": "3", "user_id": "4", "timestamp": 1})),
+        ]);
+        let (mut events,handle) = conn.into_stream();
+        assert_eq!(handle.session_id,"session");
+        match events.next().await{
+            Some(Ok(model::ReceivableEvent::Ready(ready))) => assert_eq!(ready.user.username,"bot"),
+            other => panic!("expected ready, got {:?}",other),
+        }
+        match events.next().await{
+            Some(Ok(model::ReceivableEvent::TypingStart(typing))) => assert_eq!((typing.user_id.0).0,4),
+            other => panic!("expected typing start, got {:?}",other),
+        }
+    }
+
+    #[cfg(feature="cache")]
+    #[tokio::test]
+    async fn initial_ready_updates_the_cache(){
+        let mut conn = connection(vec![]);
+        let cache = crate::cache::Cache::default();
+        conn.set_cache(cache.clone());
+        assert!(cache.current_user().is_none());
+        conn.next_event().await.unwrap().unwrap();
+        assert_eq!(cache.current_user().map(|user| user.username),Some("bot".to_string()));
+    }
+}
@@ -0,0 +1,365 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use crate::{
+    ConnectionHandle,
+    Error,
+    model::{self,ReceivableEvent},
+    rest_client::Client,
+};
+
+use tracing::*;
+
+///Passed to every `EventHandler` method
+#[derive(Clone)]
+pub struct Context{
+    pub client: Client,
+    pub connection: ConnectionHandle,
+}
+
+///Handles gateway events, with one method per event type.
+///Every method does nothing by default, so only the events of interest need to be implemented.
+#[async_trait]
+pub trait EventHandler: Send + Sync{
+    ///contains the initial state information, also sent after re-identifying on a reconnect
+    async fn ready(&self, _ctx: Context, _event: model::Ready) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///response to Resume, sent after a reconnect resumed the previous session
+    async fn resumed(&self, _ctx: Context, _event: model::Resumed) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///new channel created
+    async fn channel_create(&self, _ctx: Context, _event: model::Channel) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///channel was updated
+    async fn channel_update(&self, _ctx: Context, _event: model::Channel) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///channel was deleted
+    async fn channel_delete(&self, _ctx: Context, _event: model::Channel) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///message was pinned or unpinned
+    async fn channel_pins_update(&self, _ctx: Context, _event: model::ChannelPinsUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///lazy-load for unavailable guild, guild became available, or user joined a new guild
+    async fn guild_create(&self, _ctx: Context, _event: model::Guild) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild was updated
+    async fn guild_update(&self, _ctx: Context, _event: model::Guild) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild became unavailable, or user left/was removed from a guild
+    async fn guild_delete(&self, _ctx: Context, _event: model::UnavailableGuild) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///user was banned from a guild
+    async fn guild_ban_add(&self, _ctx: Context, _event: model::GuildBanAdd) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///user was unbanned from a guild
+    async fn guild_ban_remove(&self, _ctx: Context, _event: model::GuildBanRemove) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild emojis were updated
+    async fn guild_emojis_update(&self, _ctx: Context, _event: model::GuildEmojisUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild integration was updated
+    async fn guild_integrations_update(&self, _ctx: Context, _event: model::GuildIntegrationsUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///new user joined a guild
+    async fn guild_member_add(&self, _ctx: Context, _event: model::GuildMemberAdd) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///user was removed from a guild
+    async fn guild_member_remove(&self, _ctx: Context, _event: model::GuildMemberRemove) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild member was updated
+    async fn guild_member_update(&self, _ctx: Context, _event: model::GuildMemberUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///response to Request Guild Members
+    async fn guild_members_chunk(&self, _ctx: Context, _event: model::GuildMembersChunk) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild role was created
+    async fn guild_role_create(&self, _ctx: Context, _event: model::GuildRoleCreate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild role was updated
+    async fn guild_role_update(&self, _ctx: Context, _event: model::GuildRoleUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild role was deleted
+    async fn guild_role_delete(&self, _ctx: Context, _event: model::GuildRoleDelete) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///message was created
+    async fn message_create(&self, _ctx: Context, _event: model::Message) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///message was edited
+    async fn message_update(&self, _ctx: Context, _event: model::MessageUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///message was deleted
+    async fn message_delete(&self, _ctx: Context, _event: model::MessageDelete) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///multiple messages were deleted at once
+    async fn message_delete_bulk(&self, _ctx: Context, _event: model::MessageDeleteBulk) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///user reacted to a message
+    async fn message_reaction_add(&self, _ctx: Context, _event: model::MessageReactionAdd) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///user removed a reaction from a message
+    async fn message_reaction_remove(&self, _ctx: Context, _event: model::MessageReactionRemove) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///all reactions were explicitly removed from a message
+    async fn message_reaction_remove_all(&self, _ctx: Context, _event: model::MessageReactionRemoveAll) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///user was updated
+    async fn presence_update(&self, _ctx: Context, _event: model::PresenceUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///user started typing in a channel
+    async fn typing_start(&self, _ctx: Context, _event: model::TypingStart) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///properties about the user changed
+    async fn user_update(&self, _ctx: Context, _event: model::User) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///someone joined, left, or moved a voice channel
+    async fn voice_state_update(&self, _ctx: Context, _event: model::VoiceState) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild's voice server was updated
+    async fn voice_server_update(&self, _ctx: Context, _event: model::VoiceServerUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///guild channel webhook was created, update, or deleted
+    async fn webhooks_update(&self, _ctx: Context, _event: model::WebhooksUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a slash command relevant to the current user was created
+    async fn application_command_create(&self, _ctx: Context, _event: model::ApplicationCommand) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a slash command relevant to the current user was updated
+    async fn application_command_update(&self, _ctx: Context, _event: model::ApplicationCommand) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a slash command relevant to the current user was deleted
+    async fn application_command_delete(&self, _ctx: Context, _event: model::ApplicationCommand) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a user used a slash command or message component
+    async fn interaction_create(&self, _ctx: Context, _event: model::Interaction) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a stage instance was created (i.e. the stage is now "live")
+    async fn stage_instance_create(&self, _ctx: Context, _event: model::StageInstance) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a stage instance was updated
+    async fn stage_instance_update(&self, _ctx: Context, _event: model::StageInstance) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a stage instance was deleted (i.e. the stage has been closed)
+    async fn stage_instance_delete(&self, _ctx: Context, _event: model::StageInstance) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a thread was created, or the current user was added to a private thread
+    async fn thread_create(&self, _ctx: Context, _event: model::Channel) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a thread was updated
+    async fn thread_update(&self, _ctx: Context, _event: model::Channel) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a thread was deleted
+    async fn thread_delete(&self, _ctx: Context, _event: model::Channel) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///the current user gained access to a channel, contains all its active threads
+    async fn thread_list_sync(&self, _ctx: Context, _event: model::ThreadListSync) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///the thread member object for the current user was updated
+    async fn thread_member_update(&self, _ctx: Context, _event: model::ThreadMemberUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///users were added to or removed from a thread
+    async fn thread_members_update(&self, _ctx: Context, _event: model::ThreadMembersUpdate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///an invite to a channel was created
+    async fn invite_create(&self, _ctx: Context, _event: model::InviteCreate) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///an invite to a channel was deleted
+    async fn invite_delete(&self, _ctx: Context, _event: model::InviteDelete) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a guild scheduled event was created
+    async fn guild_scheduled_event_create(&self, _ctx: Context, _event: model::GuildScheduledEvent) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a guild scheduled event was updated
+    async fn guild_scheduled_event_update(&self, _ctx: Context, _event: model::GuildScheduledEvent) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a guild scheduled event was deleted
+    async fn guild_scheduled_event_delete(&self, _ctx: Context, _event: model::GuildScheduledEvent) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a user subscribed to a guild scheduled event
+    async fn guild_scheduled_event_user_add(&self, _ctx: Context, _event: model::ScheduledEventSubscription) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///a user unsubscribed from a guild scheduled event
+    async fn guild_scheduled_event_user_remove(&self, _ctx: Context, _event: model::ScheduledEventSubscription) -> Result<(),Error>{
+        Ok(())
+    }
+
+    ///an event we don't have a type for yet
+    async fn unknown(&self, _ctx: Context, _name: String, _value: serde_json::Value) -> Result<(),Error>{
+        Ok(())
+    }
+}
+
+async fn dispatch(handler: &dyn EventHandler, ctx: Context, event: ReceivableEvent) -> Result<(),Error>{
+    match event{
+        ReceivableEvent::Ready(event) => handler.ready(ctx,event).await,
+        ReceivableEvent::Resumed(event) => handler.resumed(ctx,event).await,
+        ReceivableEvent::ChannelCreate(event) => handler.channel_create(ctx,event).await,
+        ReceivableEvent::ChannelUpdate(event) => handler.channel_update(ctx,event).await,
+        ReceivableEvent::ChannelDelete(event) => handler.channel_delete(ctx,event).await,
+        ReceivableEvent::ChannelPinsUpdate(event) => handler.channel_pins_update(ctx,event).await,
+        ReceivableEvent::GuildCreate(event) => handler.guild_create(ctx,event).await,
+        ReceivableEvent::GuildUpdate(event) => handler.guild_update(ctx,event).await,
+        ReceivableEvent::GuildDelete(event) => handler.guild_delete(ctx,event).await,
+        ReceivableEvent::GuildBanAdd(event) => handler.guild_ban_add(ctx,event).await,
+        ReceivableEvent::GuildBanRemove(event) => handler.guild_ban_remove(ctx,event).await,
+        ReceivableEvent::GuildEmojisUpdate(event) => handler.guild_emojis_update(ctx,event).await,
+        ReceivableEvent::GuildIntegrationsUpdate(event) => handler.guild_integrations_update(ctx,event).await,
+        ReceivableEvent::GuildMemberAdd(event) => handler.guild_member_add(ctx,event).await,
+        ReceivableEvent::GuildMemberRemove(event) => handler.guild_member_remove(ctx,event).await,
+        ReceivableEvent::GuildMemberUpdate(event) => handler.guild_member_update(ctx,event).await,
+        ReceivableEvent::GuildMembersChunk(event) => handler.guild_members_chunk(ctx,event).await,
+        ReceivableEvent::GuildRoleCreate(event) => handler.guild_role_create(ctx,event).await,
+        ReceivableEvent::GuildRoleUpdate(event) => handler.guild_role_update(ctx,event).await,
+        ReceivableEvent::GuildRoleDelete(event) => handler.guild_role_delete(ctx,event).await,
+        ReceivableEvent::MessageCreate(event) => handler.message_create(ctx,event).await,
+        ReceivableEvent::MessageUpdate(event) => handler.message_update(ctx,event).await,
+        ReceivableEvent::MessageDelete(event) => handler.message_delete(ctx,event).await,
+        ReceivableEvent::MessageDeleteBulk(event) => handler.message_delete_bulk(ctx,event).await,
+        ReceivableEvent::MessageReactionAdd(event) => handler.message_reaction_add(ctx,event).await,
+        ReceivableEvent::MessageReactionRemove(event) => handler.message_reaction_remove(ctx,event).await,
+        ReceivableEvent::MessageReactionRemoveAll(event) => handler.message_reaction_remove_all(ctx,event).await,
+        ReceivableEvent::PresenceUpdate(event) => handler.presence_update(ctx,event).await,
+        ReceivableEvent::TypingStart(event) => handler.typing_start(ctx,event).await,
+        ReceivableEvent::UserUpdate(event) => handler.user_update(ctx,event).await,
+        ReceivableEvent::VoiceStateUpdate(event) => handler.voice_state_update(ctx,event).await,
+        ReceivableEvent::VoiceServerUpdate(event) => handler.voice_server_update(ctx,event).await,
+        ReceivableEvent::WebhooksUpdate(event) => handler.webhooks_update(ctx,event).await,
+        ReceivableEvent::ApplicationCommandCreate(event) => handler.application_command_create(ctx,event).await,
+        ReceivableEvent::ApplicationCommandUpdate(event) => handler.application_command_update(ctx,event).await,
+        ReceivableEvent::ApplicationCommandDelete(event) => handler.application_command_delete(ctx,event).await,
+        ReceivableEvent::InteractionCreate(event) => handler.interaction_create(ctx,event).await,
+        ReceivableEvent::StageInstanceCreate(event) => handler.stage_instance_create(ctx,event).await,
+        ReceivableEvent::StageInstanceUpdate(event) => handler.stage_instance_update(ctx,event).await,
+        ReceivableEvent::StageInstanceDelete(event) => handler.stage_instance_delete(ctx,event).await,
+        ReceivableEvent::ThreadCreate(event) => handler.thread_create(ctx,event).await,
+        ReceivableEvent::ThreadUpdate(event) => handler.thread_update(ctx,event).await,
+        ReceivableEvent::ThreadDelete(event) => handler.thread_delete(ctx,event).await,
+        ReceivableEvent::ThreadListSync(event) => handler.thread_list_sync(ctx,event).await,
+        ReceivableEvent::ThreadMemberUpdate(event) => handler.thread_member_update(ctx,event).await,
+        ReceivableEvent::ThreadMembersUpdate(event) => handler.thread_members_update(ctx,event).await,
+        ReceivableEvent::InviteCreate(event) => handler.invite_create(ctx,event).await,
+        ReceivableEvent::InviteDelete(event) => handler.invite_delete(ctx,event).await,
+        ReceivableEvent::GuildScheduledEventCreate(event) => handler.guild_scheduled_event_create(ctx,event).await,
+        ReceivableEvent::GuildScheduledEventUpdate(event) => handler.guild_scheduled_event_update(ctx,event).await,
+        ReceivableEvent::GuildScheduledEventDelete(event) => handler.guild_scheduled_event_delete(ctx,event).await,
+        ReceivableEvent::GuildScheduledEventUserAdd(event) => handler.guild_scheduled_event_user_add(ctx,event).await,
+        ReceivableEvent::GuildScheduledEventUserRemove(event) => handler.guild_scheduled_event_user_remove(ctx,event).await,
+        ReceivableEvent::Unknown{name,value} => handler.unknown(ctx,name,value).await,
+    }
+}
+
+///Passes an event to each handler in turn, in the order they were registered
+pub (crate) async fn dispatch_all(handlers: Arc<Vec<Arc<dyn EventHandler>>>, ctx: Context, event: ReceivableEvent){
+    if let Some((last,rest)) = handlers.split_last(){
+        for handler in rest{
+            if let Err(e) = dispatch(handler.as_ref(),ctx.clone(),event.clone()).await{
+                warn!("event handler error {:?}",e);
+            }
+        }
+        if let Err(e) = dispatch(last.as_ref(),ctx,event).await{
+            warn!("event handler error {:?}",e);
+        }
+    }
+}
@@ -8,12 +8,18 @@ use thiserror::Error;
 pub use discord_next_model as model;
 pub use discord_next_rest as rest_client;
 
+#[cfg(feature = "cache")]
+pub mod cache;
 mod close_on_drop;
 mod connection;
 mod extensions;
+mod handler;
+mod shard;
 #[cfg(feature = "voice")]
 pub mod voice;
 pub use connection::*;
+pub use handler::*;
+pub use shard::*;
 
 pub(crate) const GATEWAY_VERSION: u8 = 8;
 
@@ -31,6 +37,10 @@ pub enum Error {
     RestError(#[from] discord_next_rest::Error),
     #[error("Gateway connection closed: {0:?}")]
     ConnectionClosed(Option<model::CloseCode>),
+    #[error("Shard {0} stopped: {1}")]
+    ShardStopped(u64, Box<Error>),
+    #[error("Not enough session starts remain to start {0} shards, resets in {1:?}")]
+    SessionStartLimit(u64, std::time::Duration),
     #[cfg(feature = "voice")]
     #[error("Voice connection closed: {0:?}")]
     VoiceConnectionClosed(Option<model::voice::CloseCode>),
@@ -0,0 +1,121 @@
+use std::time::Duration;
+use futures::{
+    prelude::*,
+    channel::mpsc::{UnboundedSender,UnboundedReceiver,unbounded},
+};
+use url::Url;
+use crate::{
+    Connection,
+    Error,
+    model,
+};
+
+use tracing::*;
+
+///discord allows `max_concurrency` identifies per this window
+const IDENTIFY_WINDOW: Duration = Duration::from_secs(5);
+
+///An event received on one of the shards of a `ShardManager`
+#[derive(Debug)]
+pub struct ShardEvent{
+    pub shard_id: u64,
+    pub event: model::ReceivableEvent,
+}
+
+///Runs one gateway `Connection` per shard, merging their events into a single stream
+pub struct ShardManager{
+    token: String,
+    gateway_url: Url,
+    shard_count: u64,
+    session_start_limit: model::SessionStartLimit,
+}
+
+impl ShardManager{
+    ///Creates a shard manager using the shard count recommended by discord
+    pub async fn new<S: Into<String>>(token: S) -> Result<Self,Error>{
+        let token = token.into();
+        let client = crate::rest_client::Client::new(token.clone());
+        let gateway_bot = client.get_gateway_bot().await?;
+        debug!("gateway bot info: {:?}",gateway_bot);
+        let gateway_url = Url::parse(format!("{}?v={}&encoding=json",gateway_bot.url,crate::GATEWAY_VERSION).as_str())?;
+        Ok(Self{
+            token,
+            gateway_url,
+            shard_count: gateway_bot.shards,
+            session_start_limit: gateway_bot.session_start_limit,
+        })
+    }
+
+    ///Overrides the recommended shard count
+    pub fn with_shard_count(mut self, shard_count: u64) -> Self{
+        self.shard_count = shard_count;
+        self
+    }
+
+    pub fn shard_count(&self) -> u64{
+        self.shard_count
+    }
+
+    ///Connects every shard in the background, returning a stream of events from all of them.
+    ///Shards are identified in groups of `max_concurrency`, waiting between groups as discord requires.
+    ///If a shard fails to connect, or stops with an error, an `Error::ShardStopped` is sent on the stream and the other shards keep running.
+    pub fn start(self) -> Result<UnboundedReceiver<Result<ShardEvent,Error>>,Error>{
+        if self.session_start_limit.remaining < self.shard_count{
+            return Err(Error::SessionStartLimit(self.shard_count,Duration::from_millis(self.session_start_limit.reset_after)));
+        }
+        let (tx,rx) = unbounded();
+        tokio::spawn(self.start_shards(tx));
+        Ok(rx)
+    }
+
+    async fn start_shards(self, tx: UnboundedSender<Result<ShardEvent,Error>>){
+        let shard_count = self.shard_count;
+        let max_concurrency = std::cmp::max(self.session_start_limit.max_concurrency,1);
+        let mut shard_ids = 0..shard_count;
+        loop{
+            //consecutive shard ids all fall in different identify buckets
+            let group: Vec<u64> = shard_ids.by_ref().take(max_concurrency as usize).collect();
+            if group.is_empty(){
+                break;
+            }
+            debug!("starting shards {:?} of {}",group,shard_count);
+            let connections = future::join_all(group.into_iter().map(|shard_id|{
+                Connection::connect_to(self.token.clone(),self.gateway_url.clone(),Some([shard_id,shard_count]))
+                    .map(move |res| (shard_id,res))
+            })).await;
+            for (shard_id,connection) in connections{
+                match connection{
+                    Ok(connection) => {
+                        tokio::spawn(run_shard(shard_id,connection,tx.clone()));
+                    }
+                    Err(e) => {
+                        warn!("shard {} failed to connect: {:?}",shard_id,e);
+                        if tx.unbounded_send(Err(Error::ShardStopped(shard_id,Box::new(e)))).is_err(){
+                            //nobody is listening any more
+                            return;
+                        }
+                    }
+                }
+            }
+            if !shard_ids.is_empty(){
+                tokio::time::delay_for(IDENTIFY_WINDOW).await;
+            }
+        }
+    }
+}
+
+async fn run_shard(shard_id: u64, connection: Connection, tx: UnboundedSender<Result<ShardEvent,Error>>){
+    let events = tx.clone();
+    let res = connection.run(move |_conn, event, _client|{
+        //forward synchronously so events from a shard stay in order
+        let _ = events.unbounded_send(Ok(ShardEvent{
+            shard_id,
+            event,
+        }));
+        future::ready(Result::<(),Error>::Ok(()))
+    }).await;
+    if let Err(e) = res{
+        warn!("shard {} stopped: {:?}",shard_id,e);
+        let _ = tx.unbounded_send(Err(Error::ShardStopped(shard_id,Box::new(e))));
+    }
+}
@@ -290,6 +290,18 @@ impl From<&crate::connection::Connection> for VoiceConnector{
     }
 }
 
+impl From<&crate::connection::ConnectionHandle> for VoiceConnector{
+    fn from(handle: &crate::connection::ConnectionHandle) -> Self{
+        Self{
+            sender: handle.clone_writer(),
+            voice_state_store: handle.voice_update_store.clone(),
+            user_id: handle.user.id,
+            session_id: handle.session_id.clone(),
+            voice_state: handle.voice_state.clone(),
+        }
+    }
+}
+
 impl VoiceConnector{
     pub fn connect(&self, guild_id: model::GuildId, channel_id: Option<model::ChannelId>) -> impl Future<Output=Result<Connection,Error>> + 'static
     {
@@ -0,0 +1,324 @@
+use crate::*;
+use serde::de::{DeserializeOwned, Deserializer};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AuditLog {
+    ///list of audit log entries, newest first
+    pub audit_log_entries: Vec<AuditLogEntry>,
+    ///list of users found in the audit log
+    #[serde(default)]
+    pub users: Vec<User>,
+    ///list of threads found in the audit log
+    #[serde(default)]
+    pub threads: Vec<Channel>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AuditLogEntry {
+    ///id of the entry
+    pub id: AuditLogEntryId,
+    ///id of the affected entity (webhook, user, role, etc.)
+    pub target_id: Option<String>,
+    ///changes made to the target
+    #[serde(default)]
+    pub changes: Vec<AuditLogChange>,
+    ///the user who made the changes
+    pub user_id: Option<UserId>,
+    ///type of action that occurred
+    pub action_type: AuditLogEvent,
+    ///additional info for certain action types
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<AuditLogEntryInfo>,
+    ///the reason for the change (0-512 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+///Declares `AuditLogEvent` with an `Other` fallback, so a new action type doesn't break deserializing a whole audit log
+macro_rules! audit_log_events {
+    ($($variant:ident = $value:literal,)+) => {
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+        pub enum AuditLogEvent {
+            $($variant,)+
+            ///an action type without a variant here yet
+            Other(u64),
+        }
+
+        impl AuditLogEvent {
+            pub fn value(self) -> u64 {
+                match self {
+                    $(AuditLogEvent::$variant => $value,)+
+                    AuditLogEvent::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<u64> for AuditLogEvent {
+            fn from(value: u64) -> Self {
+                match value {
+                    $($value => AuditLogEvent::$variant,)+
+                    other => AuditLogEvent::Other(other),
+                }
+            }
+        }
+    };
+}
+
+audit_log_events! {
+    GuildUpdate = 1,
+    ChannelCreate = 10,
+    ChannelUpdate = 11,
+    ChannelDelete = 12,
+    ChannelOverwriteCreate = 13,
+    ChannelOverwriteUpdate = 14,
+    ChannelOverwriteDelete = 15,
+    MemberKick = 20,
+    MemberPrune = 21,
+    MemberBanAdd = 22,
+    MemberBanRemove = 23,
+    MemberUpdate = 24,
+    MemberRoleUpdate = 25,
+    MemberMove = 26,
+    MemberDisconnect = 27,
+    BotAdd = 28,
+    RoleCreate = 30,
+    RoleUpdate = 31,
+    RoleDelete = 32,
+    InviteCreate = 40,
+    InviteUpdate = 41,
+    InviteDelete = 42,
+    WebhookCreate = 50,
+    WebhookUpdate = 51,
+    WebhookDelete = 52,
+    EmojiCreate = 60,
+    EmojiUpdate = 61,
+    EmojiDelete = 62,
+    MessageDelete = 72,
+    MessageBulkDelete = 73,
+    MessagePin = 74,
+    MessageUnpin = 75,
+    IntegrationCreate = 80,
+    IntegrationUpdate = 81,
+    IntegrationDelete = 82,
+    StageInstanceCreate = 83,
+    StageInstanceUpdate = 84,
+    StageInstanceDelete = 85,
+    StickerCreate = 90,
+    StickerUpdate = 91,
+    StickerDelete = 92,
+    GuildScheduledEventCreate = 100,
+    GuildScheduledEventUpdate = 101,
+    GuildScheduledEventDelete = 102,
+    ThreadCreate = 110,
+    ThreadUpdate = 111,
+    ThreadDelete = 112,
+}
+
+impl Serialize for AuditLogEvent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u64(self.value())
+    }
+}
+
+impl<'de> Deserialize<'de> for AuditLogEvent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(u64::deserialize(deserializer)?.into())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AuditLogEntryInfo {
+    ///number of days after which inactive members were kicked (MEMBER_PRUNE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delete_member_days: Option<String>,
+    ///number of members removed by the prune (MEMBER_PRUNE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members_removed: Option<String>,
+    ///channel in which the entities were targeted (MEMBER_MOVE, MESSAGE_PIN, MESSAGE_UNPIN, MESSAGE_DELETE, STAGE_INSTANCE_*)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    ///id of the message that was targeted (MESSAGE_PIN, MESSAGE_UNPIN)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<MessageId>,
+    ///number of entities that were targeted (MESSAGE_DELETE, MESSAGE_BULK_DELETE, MEMBER_DISCONNECT, MEMBER_MOVE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<String>,
+    ///id of the overwritten entity (CHANNEL_OVERWRITE_*)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Snowflake>,
+    ///type of overwritten entity, "0" for role or "1" for member (CHANNEL_OVERWRITE_*)
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    ///name of the role if type is "0" (CHANNEL_OVERWRITE_*)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_name: Option<String>,
+}
+
+///The value of a changed field before and after the change
+#[derive(Debug, Clone)]
+pub struct Change<T> {
+    pub old: Option<T>,
+    pub new: Option<T>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AuditLogRole {
+    ///role id
+    pub id: RoleId,
+    ///role name
+    pub name: String,
+}
+
+///A change to a single field of the target of an audit log entry
+///
+///Keys without their own variant are kept as raw json in `Other`
+#[derive(Debug, Clone)]
+pub enum AuditLogChange {
+    Name(Change<String>),
+    Description(Change<String>),
+    Topic(Change<String>),
+    Nick(Change<String>),
+    Code(Change<String>),
+    Icon(Change<String>),
+    Avatar(Change<String>),
+    OwnerId(Change<UserId>),
+    ChannelId(Change<ChannelId>),
+    Position(Change<u64>),
+    Permissions(Change<Permissions>),
+    Allow(Change<Permissions>),
+    Deny(Change<Permissions>),
+    Color(Change<u32>),
+    Hoist(Change<bool>),
+    Mentionable(Change<bool>),
+    Nsfw(Change<bool>),
+    RateLimitPerUser(Change<u64>),
+    Deaf(Change<bool>),
+    Mute(Change<bool>),
+    ///roles added to a member
+    RolesAdded(Vec<AuditLogRole>),
+    ///roles removed from a member
+    RolesRemoved(Vec<AuditLogRole>),
+    Other {
+        key: String,
+        old_value: Option<serde_json::Value>,
+        new_value: Option<serde_json::Value>,
+    },
+}
+
+#[derive(Deserialize)]
+struct RawAuditLogChange {
+    key: String,
+    #[serde(default)]
+    old_value: Option<serde_json::Value>,
+    #[serde(default)]
+    new_value: Option<serde_json::Value>,
+}
+
+impl RawAuditLogChange {
+    fn change<T: DeserializeOwned>(self) -> Result<Change<T>, serde_json::Error> {
+        Ok(Change {
+            old: self.old_value.map(serde_json::from_value).transpose()?,
+            new: self.new_value.map(serde_json::from_value).transpose()?,
+        })
+    }
+}
+
+impl<'de> serde::de::Deserialize<'de> for AuditLogChange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let raw = RawAuditLogChange::deserialize(deserializer)?;
+        let change = match raw.key.as_str() {
+            "name" => raw.change().map(AuditLogChange::Name),
+            "description" => raw.change().map(AuditLogChange::Description),
+            "topic" => raw.change().map(AuditLogChange::Topic),
+            "nick" => raw.change().map(AuditLogChange::Nick),
+            "code" => raw.change().map(AuditLogChange::Code),
+            "icon_hash" => raw.change().map(AuditLogChange::Icon),
+            "avatar_hash" => raw.change().map(AuditLogChange::Avatar),
+            "owner_id" => raw.change().map(AuditLogChange::OwnerId),
+            "channel_id" => raw.change().map(AuditLogChange::ChannelId),
+            "position" => raw.change().map(AuditLogChange::Position),
+            "permissions" => raw.change().map(AuditLogChange::Permissions),
+            "allow" => raw.change().map(AuditLogChange::Allow),
+            "deny" => raw.change().map(AuditLogChange::Deny),
+            "color" => raw.change().map(AuditLogChange::Color),
+            "hoist" => raw.change().map(AuditLogChange::Hoist),
+            "mentionable" => raw.change().map(AuditLogChange::Mentionable),
+            "nsfw" => raw.change().map(AuditLogChange::Nsfw),
+            "rate_limit_per_user" => raw.change().map(AuditLogChange::RateLimitPerUser),
+            "deaf" => raw.change().map(AuditLogChange::Deaf),
+            "mute" => raw.change().map(AuditLogChange::Mute),
+            "$add" => serde_json::from_value(raw.new_value.unwrap_or_default())
+                .map(AuditLogChange::RolesAdded),
+            "$remove" => serde_json::from_value(raw.new_value.unwrap_or_default())
+                .map(AuditLogChange::RolesRemoved),
+            _other => Ok(AuditLogChange::Other {
+                key: raw.key,
+                old_value: raw.old_value,
+                new_value: raw.new_value,
+            }),
+        };
+        change.map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn changes_are_typed_by_key() {
+        let entry: AuditLogEntry = serde_json::from_value(serde_json::json!({
+            "id": "3",
+            "target_id": "2",
+            "user_id": "1",
+            "action_type": 25,
+            "changes": [
+                {"key": "$add", "new_value": [{"id": "4", "name": "mod"}]},
+                {"key": "nick", "old_value": "a", "new_value": "b"},
+                {"key": "something_new", "new_value": 5},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(entry.action_type, AuditLogEvent::MemberRoleUpdate);
+        match &entry.changes[..] {
+            [AuditLogChange::RolesAdded(roles), AuditLogChange::Nick(nick), AuditLogChange::Other { key, .. }] =>
+            {
+                assert_eq!(roles[0].id, RoleId(Snowflake(4)));
+                assert_eq!(nick.new.as_deref(), Some("b"));
+                assert_eq!(key, "something_new");
+            }
+            other => panic!("unexpected changes {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_action_types_are_kept() {
+        let log: AuditLog = serde_json::from_value(serde_json::json!({
+            "audit_log_entries": [
+                {"id": "5", "target_id": null, "user_id": "1", "action_type": 121},
+                {"id": "4", "target_id": null, "user_id": "1", "action_type": 20},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(
+            log.audit_log_entries[0].action_type,
+            AuditLogEvent::Other(121)
+        );
+        assert_eq!(log.audit_log_entries[0].action_type.value(), 121);
+        assert_eq!(
+            log.audit_log_entries[1].action_type,
+            AuditLogEvent::MemberKick
+        );
+    }
+}
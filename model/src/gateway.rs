@@ -168,10 +168,11 @@ pub struct RequestGuildMembers {
 #[derive(Debug, Serialize)]
 pub struct Resume {
     //session token
-    token: String,
-    session_id: String,
+    pub token: String,
+    //session id
+    pub session_id: String,
     //last sequence number received
-    seq: u64,
+    pub seq: u64,
 }
 
 #[derive(Debug)]
@@ -212,10 +213,11 @@ wrapping_from!(GatewayEvent, InvalidSession, expect_invalid_session);
 
 #[derive(Debug)]
 pub struct InvalidSession {
-    resumable: bool,
+    ///whether the session may be resumed
+    pub resumable: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub enum ReceivableEvent {
     //contains the initial state information
     Ready(Ready),
@@ -297,6 +299,32 @@ pub enum ReceivableEvent {
     StageInstanceUpdate(StageInstance),
     //Sent when a Stage instance has been deleted (i.e. the Stage has been closed). Inner payload is a Stage instance
     StageInstanceDelete(StageInstance),
+    //a thread was created, or the current user was added to a private thread
+    ThreadCreate(Channel),
+    //a thread was updated
+    ThreadUpdate(Channel),
+    //a thread was deleted, only the id, guild_id, parent_id and type are set
+    ThreadDelete(Channel),
+    //sent when gaining access to a channel, contains all active threads in that channel
+    ThreadListSync(ThreadListSync),
+    //the thread member object for the current user was updated
+    ThreadMemberUpdate(ThreadMemberUpdate),
+    //users were added to or removed from a thread
+    ThreadMembersUpdate(ThreadMembersUpdate),
+    //an invite to a channel was created
+    InviteCreate(InviteCreate),
+    //an invite to a channel was deleted
+    InviteDelete(InviteDelete),
+    //a guild scheduled event was created
+    GuildScheduledEventCreate(GuildScheduledEvent),
+    //a guild scheduled event was updated
+    GuildScheduledEventUpdate(GuildScheduledEvent),
+    //a guild scheduled event was deleted
+    GuildScheduledEventDelete(GuildScheduledEvent),
+    //a user subscribed to a guild scheduled event
+    GuildScheduledEventUserAdd(ScheduledEventSubscription),
+    //a user unsubscribed from a guild scheduled event
+    GuildScheduledEventUserRemove(ScheduledEventSubscription),
     //Holds unkown events for limited forwards compatibility
     Unknown {
         name: String,
@@ -372,6 +400,19 @@ wrapping_from!(
     expect_voice_server_update
 );
 wrapping_from!(ReceivableEvent, WebhooksUpdate, expect_webhooks_update);
+wrapping_from!(ReceivableEvent, ThreadListSync, expect_thread_list_sync);
+wrapping_from!(
+    ReceivableEvent,
+    ThreadMemberUpdate,
+    expect_thread_member_update
+);
+wrapping_from!(
+    ReceivableEvent,
+    ThreadMembersUpdate,
+    expect_thread_members_update
+);
+wrapping_from!(ReceivableEvent, InviteCreate, expect_invite_create);
+wrapping_from!(ReceivableEvent, InviteDelete, expect_invite_delete);
 
 //TODO: replace this mess with better macros or codegen if possible
 impl ReceivableEvent {
@@ -438,6 +479,19 @@ impl ReceivableEvent {
                 "STAGE_INSTANCE_CREATE" => StageInstanceCreate,
                 "STAGE_INSTANCE_UPDATE" => StageInstanceUpdate,
                 "STAGE_INSTANCE_DELETE" => StageInstanceDelete,
+                "THREAD_CREATE" => ThreadCreate,
+                "THREAD_UPDATE" => ThreadUpdate,
+                "THREAD_DELETE" => ThreadDelete,
+                "THREAD_LIST_SYNC" => ThreadListSync,
+                "THREAD_MEMBER_UPDATE" => ThreadMemberUpdate,
+                "THREAD_MEMBERS_UPDATE" => ThreadMembersUpdate,
+                "INVITE_CREATE" => InviteCreate,
+                "INVITE_DELETE" => InviteDelete,
+                "GUILD_SCHEDULED_EVENT_CREATE" => GuildScheduledEventCreate,
+                "GUILD_SCHEDULED_EVENT_UPDATE" => GuildScheduledEventUpdate,
+                "GUILD_SCHEDULED_EVENT_DELETE" => GuildScheduledEventDelete,
+                "GUILD_SCHEDULED_EVENT_USER_ADD" => GuildScheduledEventUserAdd,
+                "GUILD_SCHEDULED_EVENT_USER_REMOVE" => GuildScheduledEventUserRemove,
             }
         }
     }
@@ -465,9 +519,9 @@ pub struct Identify {
     //value between 50 and 250, total number of members where the gateway will stop sending offline members in the guild member list
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub large_threshold: Option<u8>,
-    //used for Guild Sharding
+    //used for Guild Sharding, as [shard_id, num_shards]
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub shard: Option<[u8; 2]>,
+    pub shard: Option<[u64; 2]>,
     //initial presence information
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub presence: Option<UpdateStatus>,
@@ -488,7 +542,7 @@ impl Identify {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct Ready {
     //gateway protocol version
     pub v: u64,
@@ -500,37 +554,63 @@ pub struct Ready {
     pub guilds: Vec<UnavailableGuild>,
     //used for resuming connections
     pub session_id: String,
+    //the shard information associated with this session, if sent when identifying
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard: Option<[u64; 2]>,
     //used for debugging
     pub _trace: Vec<String>,
 }
 
-///response to Resume
+///response to `GET /gateway/bot`
+#[derive(Debug, Deserialize)]
+pub struct GatewayBot {
+    ///the WSS URL that can be used for connecting to the gateway
+    pub url: String,
+    ///the recommended number of shards to use when connecting
+    pub shards: u64,
+    ///information on the current session start limit
+    pub session_start_limit: SessionStartLimit,
+}
+
 #[derive(Debug, Deserialize)]
+pub struct SessionStartLimit {
+    ///the total number of session starts the current user is allowed
+    pub total: u64,
+    ///the remaining number of session starts the current user is allowed
+    pub remaining: u64,
+    ///the number of milliseconds after which the limit resets
+    pub reset_after: u64,
+    ///the number of identify requests allowed per 5 seconds
+    pub max_concurrency: u64,
+}
+
+///response to Resume
+#[derive(Debug, Deserialize, Clone)]
 pub struct Resumed {
     //used for debugging
     pub _trace: Vec<String>,
 }
 ///message was pinned or unpinned
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct ChannelPinsUpdate {
     channel_id: ChannelId,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     last_pin_timestamp: Option<DateTime<FixedOffset>>,
 }
 ///user was banned from a guild
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildBanAdd {
     guild_id: GuildId,
     user: User,
 }
 ///user was unbanned from a guild
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildBanRemove {
     guild_id: GuildId,
     user: User,
 }
 ///guild emojis were updated
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildEmojisUpdate {
     ///id of the guild
     guild_id: GuildId,
@@ -538,79 +618,79 @@ pub struct GuildEmojisUpdate {
     emojis: Vec<Emoji>,
 }
 ///guild integration was updated
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildIntegrationsUpdate {
     ///id of the guild whose integrations were updated
     guild_id: GuildId,
 }
 ///new user joined a guild
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildMemberAdd {
     ///id of the guild
-    guild_id: GuildId,
+    pub guild_id: GuildId,
     #[serde(flatten)]
-    member: GuildMember,
+    pub member: GuildMember,
 }
 ///user was removed from a guild
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildMemberRemove {
     ///the id of the guild
-    guild_id: GuildId,
+    pub guild_id: GuildId,
     ///the user who was removed
-    user: User,
+    pub user: User,
 }
 ///guild member was updated
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildMemberUpdate {
     ///the id of the guild
-    guild_id: GuildId,
+    pub guild_id: GuildId,
     ///user role ids
-    roles: Vec<RoleId>,
+    pub roles: Vec<RoleId>,
     ///the user
-    user: User,
+    pub user: User,
     ///nickname of the user in the guild
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    nick: Option<String>,
+    pub nick: Option<String>,
 }
 ///response to Request Guild Members
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildMembersChunk {
     ///the id of the guild
-    guild_id: GuildId,
+    pub guild_id: GuildId,
     ///set of guild members
-    members: Vec<GuildMember>,
+    pub members: Vec<GuildMember>,
 }
 ///guild role was created
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildRoleCreate {
     ///the id of the guild
-    guild_id: GuildId,
+    pub guild_id: GuildId,
     ///the role created
-    role: Role,
+    pub role: Role,
 }
 ///guild role was updated
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildRoleUpdate {
     ///the id of the guild
-    guild_id: GuildId,
+    pub guild_id: GuildId,
     ///the role updated
-    role: Role,
+    pub role: Role,
 }
 ///guild role was deleted
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct GuildRoleDelete {
     ///id of the guild
-    guild_id: GuildId,
+    pub guild_id: GuildId,
     ///id of the role
-    role_id: RoleId,
+    pub role_id: RoleId,
 }
 ///message was edited
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct MessageUpdate {
     //TODO: partial Message
 }
 ///message was deleted
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct MessageDelete {
     ///the id of the message
     pub id: MessageId,
@@ -621,7 +701,7 @@ pub struct MessageDelete {
     pub guild_id: Option<GuildId>,
 }
 ///multiple messages were deleted at once
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct MessageDeleteBulk {
     ///the ids of the messages
     pub ids: Vec<MessageId>,
@@ -632,7 +712,7 @@ pub struct MessageDeleteBulk {
     pub guild_id: Option<GuildId>,
 }
 ///user reacted to a message
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct MessageReactionAdd {
     ///the id of the user
     pub user_id: UserId,
@@ -647,7 +727,7 @@ pub struct MessageReactionAdd {
     pub emoji: Emoji,
 }
 ///user removed a reaction from a message
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct MessageReactionRemove {
     ///the id of the user
     pub user_id: UserId,
@@ -662,7 +742,7 @@ pub struct MessageReactionRemove {
     pub emoji: Emoji,
 }
 ///all reactions were explicitly removed from a message
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct MessageReactionRemoveAll {
     ///the id of the channel
     pub channel_id: ChannelId,
@@ -673,7 +753,7 @@ pub struct MessageReactionRemoveAll {
     pub guild_id: Option<GuildId>,
 }
 ///user started typing in a channel
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct TypingStart {
     ///id of the channel
     pub channel_id: ChannelId,
@@ -686,7 +766,7 @@ pub struct TypingStart {
     pub timestamp: u64,
 }
 ///guild's voice server was updated
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct VoiceServerUpdate {
     ///voice connection token
     pub token: String,
@@ -696,13 +776,101 @@ pub struct VoiceServerUpdate {
     pub endpoint: String,
 }
 ///guild channel webhook was created, update, or deleted
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct WebhooksUpdate {
     ///id of the guild
     pub guild_id: GuildId,
     ///id of the channel
     pub channel_id: ChannelId,
 }
+///sent when gaining access to a channel, contains all active threads in that channel
+#[derive(Debug, Deserialize, Clone)]
+pub struct ThreadListSync {
+    ///id of the guild
+    pub guild_id: GuildId,
+    ///the parent channel ids whose threads are being synced, if omitted then threads were synced for the entire guild
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_ids: Option<Vec<ChannelId>>,
+    ///all active threads in the given channels that the current user can access
+    pub threads: Vec<Channel>,
+    ///all thread member objects from the synced threads for the current user
+    pub members: Vec<ThreadMember>,
+}
+///the thread member object for the current user was updated
+#[derive(Debug, Deserialize, Clone)]
+pub struct ThreadMemberUpdate {
+    ///id of the guild
+    pub guild_id: GuildId,
+    #[serde(flatten)]
+    pub member: ThreadMember,
+}
+///users were added to or removed from a thread
+#[derive(Debug, Deserialize, Clone)]
+pub struct ThreadMembersUpdate {
+    ///the id of the thread
+    pub id: ChannelId,
+    ///id of the guild
+    pub guild_id: GuildId,
+    ///the approximate number of members in the thread, stops counting at 50
+    pub member_count: u64,
+    ///the users who were added to the thread
+    #[serde(default)]
+    pub added_members: Vec<ThreadMember>,
+    ///the id of the users who were removed from the thread
+    #[serde(default)]
+    pub removed_member_ids: Vec<UserId>,
+}
+///an invite to a channel was created
+#[derive(Debug, Deserialize, Clone)]
+pub struct InviteCreate {
+    ///the channel the invite is for
+    pub channel_id: ChannelId,
+    ///the unique invite code
+    pub code: String,
+    ///the time at which the invite was created
+    pub created_at: DateTime<FixedOffset>,
+    ///the guild of the invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    ///the user that created the invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inviter: Option<User>,
+    ///how long the invite is valid for (in seconds)
+    pub max_age: u64,
+    ///the maximum number of times the invite can be used
+    pub max_uses: u64,
+    ///the type of target for this voice channel invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<InviteTargetType>,
+    ///the user whose stream to display for this voice channel stream invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_user: Option<User>,
+    ///whether or not the invite is temporary (invited users will be kicked on disconnect unless they're assigned a role)
+    pub temporary: bool,
+    ///how many times the invite has been used (always will be 0)
+    pub uses: u64,
+}
+///an invite to a channel was deleted
+#[derive(Debug, Deserialize, Clone)]
+pub struct InviteDelete {
+    ///the channel of the invite
+    pub channel_id: ChannelId,
+    ///the guild of the invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    ///the unique invite code
+    pub code: String,
+}
+///a user subscribed to or unsubscribed from a guild scheduled event
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScheduledEventSubscription {
+    ///id of the guild scheduled event
+    pub guild_scheduled_event_id: ScheduledEventId,
+    ///id of the user
+    pub user_id: UserId,
+    ///id of the guild
+    pub guild_id: GuildId,
+}
 
 #[derive(Debug)]
 pub enum CloseCode {
@@ -728,6 +896,35 @@ pub enum CloseCode {
     InvalidShard,
     ///The session would have handled too many guilds - you are required to shard your connection in order to connect.
     ShardingRequired,
+    ///You sent an invalid version for the gateway.
+    InvalidApiVersion,
+    ///You sent an invalid intent for a Gateway Intent.
+    InvalidIntents,
+    ///You sent a disallowed intent for a Gateway Intent.
+    DisallowedIntents,
+}
+
+impl CloseCode {
+    ///whether it is worth reconnecting after the gateway closed with this code
+    pub fn can_reconnect(&self) -> bool {
+        match self {
+            CloseCode::AuthenticationFailed
+            | CloseCode::InvalidShard
+            | CloseCode::ShardingRequired
+            | CloseCode::InvalidApiVersion
+            | CloseCode::InvalidIntents
+            | CloseCode::DisallowedIntents => false,
+            _other => true,
+        }
+    }
+
+    ///whether the previous session can be resumed, or a new one must be identified
+    pub fn can_resume(&self) -> bool {
+        match self {
+            CloseCode::InvalidSeq | CloseCode::SessionTimeout => false,
+            other => other.can_reconnect(),
+        }
+    }
 }
 
 impl TryFrom<u16> for CloseCode {
@@ -746,6 +943,9 @@ impl TryFrom<u16> for CloseCode {
             4009 => CloseCode::SessionTimeout,
             4010 => CloseCode::InvalidShard,
             4011 => CloseCode::ShardingRequired,
+            4012 => CloseCode::InvalidApiVersion,
+            4013 => CloseCode::InvalidIntents,
+            4014 => CloseCode::DisallowedIntents,
             _else => return Err(()),
         })
     }
@@ -1,17 +1,118 @@
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
 
-#[derive(Hash, Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+///Milliseconds between the unix epoch and the first second of 2015, which snowflake timestamps count from
+pub const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+#[derive(Hash, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[serde(transparent)]
 pub struct Snowflake(
     #[serde(deserialize_with = "crate::custom_serialization::u64_from_string")] pub u64,
 );
 
+impl Snowflake {
+    ///When this id was generated
+    pub fn created_at(self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(((self.0 >> 22) + DISCORD_EPOCH) as i64)
+            .unwrap()
+    }
+
+    pub fn worker_id(self) -> u8 {
+        ((self.0 & 0x3E_0000) >> 17) as u8
+    }
+
+    pub fn process_id(self) -> u8 {
+        ((self.0 & 0x1_F000) >> 12) as u8
+    }
+
+    ///incremented for every id generated on the same process
+    pub fn increment(self) -> u16 {
+        (self.0 & 0xFFF) as u16
+    }
+
+    ///The lowest id that could have been generated at `time`, for use as a `before`/`after` cursor
+    ///
+    ///Times before the discord epoch give an id of 0
+    pub fn from_timestamp<Tz: TimeZone>(time: DateTime<Tz>) -> Self {
+        let millis = (time.timestamp_millis().max(0) as u64).saturating_sub(DISCORD_EPOCH);
+        Snowflake(millis << 22)
+    }
+}
+
+impl fmt::Display for Snowflake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Snowflake {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Snowflake)
+    }
+}
+
+impl From<u64> for Snowflake {
+    fn from(id: u64) -> Self {
+        Snowflake(id)
+    }
+}
+
 macro_rules! define_typed_ids {
     ($($name:ident,)+) => {
         $(
-            #[derive(Hash, Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+            #[derive(Hash, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
             #[serde(transparent)]
             pub struct $name(pub Snowflake);
+
+            impl $name {
+                ///When this id was generated
+                pub fn created_at(self) -> DateTime<Utc> {
+                    self.0.created_at()
+                }
+
+                pub fn worker_id(self) -> u8 {
+                    self.0.worker_id()
+                }
+
+                pub fn process_id(self) -> u8 {
+                    self.0.process_id()
+                }
+
+                ///incremented for every id generated on the same process
+                pub fn increment(self) -> u16 {
+                    self.0.increment()
+                }
+
+                ///The lowest id that could have been generated at `time`, for use as a `before`/`after` cursor
+                pub fn from_timestamp<Tz: TimeZone>(time: DateTime<Tz>) -> Self {
+                    $name(Snowflake::from_timestamp(time))
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    self.0.fmt(f)
+                }
+            }
+
+            impl FromStr for $name {
+                type Err = ParseIntError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    s.parse().map($name)
+                }
+            }
+
+            impl From<Snowflake> for $name {
+                fn from(id: Snowflake) -> Self {
+                    $name(id)
+                }
+            }
         )+
     };
 }
@@ -30,4 +131,26 @@ define_typed_ids! {
     StageInstanceId,
     WebhookId,
     InteractionId,
+    AuditLogEntryId,
+    ScheduledEventId,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snowflake_parts() {
+        //the example from discord's reference docs
+        let id: UserId = "175928847299117063".parse().unwrap();
+        assert_eq!(id.created_at().timestamp_millis(), 1_462_015_105_796);
+        assert_eq!(id.worker_id(), 1);
+        assert_eq!(id.process_id(), 0);
+        assert_eq!(id.increment(), 7);
+        assert_eq!(id.to_string(), "175928847299117063");
+
+        let cutoff = MessageId::from_timestamp(id.created_at());
+        assert_eq!(cutoff.created_at(), id.created_at());
+        assert!(cutoff < MessageId(id.0));
+    }
 }
@@ -21,6 +21,9 @@ mod enum_number;
 
 mod custom_serialization;
 
+mod audit_log;
+pub use audit_log::*;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ConnectionProperties {
     #[serde(rename = "$os")]
@@ -41,7 +44,7 @@ impl Default for ConnectionProperties {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub enum Status {
     #[serde(rename = "online")]
     Online,
@@ -83,7 +86,7 @@ impl UpdateStatus {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Activity {
     //the activity's name
     pub name: String,
@@ -158,7 +161,7 @@ impl serde::ser::Serialize for ActivityFlags {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ActivitySecrets {
     ///the secret for joining a party
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -172,7 +175,7 @@ pub struct ActivitySecrets {
     pub match_secret: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Timestamps {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub start: Option<u64>,
@@ -180,7 +183,7 @@ pub struct Timestamps {
     pub end: Option<u64>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Party {
     //the id of the party
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -190,7 +193,7 @@ pub struct Party {
     pub size: Option<(u64, u64)>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Assets {
     //the id for a large asset of the activity, usually a snowflake
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -206,7 +209,7 @@ pub struct Assets {
     pub small_text: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct UnavailableGuild {
     pub id: GuildId,
     pub unavailable: bool,
@@ -292,7 +295,7 @@ enum_number!(OverwriteType{
     Member = 1,
 });
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Overwrite {
     //role or user id
     pub id: Snowflake,
@@ -307,6 +310,145 @@ pub struct Overwrite {
     pub deny: u64,
 }
 
+impl Overwrite {
+    fn apply(&self, mut permissions: Permissions) -> Permissions {
+        permissions.remove(Permissions::from_bits_truncate(self.deny));
+        permissions.insert(Permissions::from_bits_truncate(self.allow));
+        permissions
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Webhook {
+    ///the id of the webhook
+    pub id: WebhookId,
+    ///the type of the webhook
+    #[serde(rename = "type")]
+    pub typ: WebhookType,
+    ///the guild id this webhook is for, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    ///the channel id this webhook is for, if any
+    pub channel_id: Option<ChannelId>,
+    ///the user this webhook was created by (not returned when getting a webhook with its token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    ///the default name of the webhook
+    pub name: Option<String>,
+    ///the default user avatar hash of the webhook
+    pub avatar: Option<String>,
+    ///the secure token of the webhook (returned for incoming webhooks)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    ///the bot/OAuth2 application that created this webhook
+    pub application_id: Option<ApplicationId>,
+    ///the url used for executing the webhook (returned by the webhooks OAuth2 flow)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+enum_number!(WebhookType{
+    Incoming = 1,
+    ChannelFollower = 2,
+    Application = 3,
+});
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FollowedChannel {
+    ///source channel id
+    pub channel_id: ChannelId,
+    ///created target webhook id
+    pub webhook_id: WebhookId,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Invite {
+    ///the invite code (unique ID)
+    pub code: String,
+    ///the guild this invite is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild: Option<PartialGuild>,
+    ///the channel this invite is for
+    pub channel: Option<InviteChannel>,
+    ///the user who created the invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inviter: Option<User>,
+    ///the type of target for this voice channel invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<InviteTargetType>,
+    ///the user whose stream to display for this voice channel stream invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_user: Option<User>,
+    ///approximate count of online members, returned when requested with counts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approximate_presence_count: Option<u64>,
+    ///approximate count of total members, returned when requested with counts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approximate_member_count: Option<u64>,
+    ///the expiration date of this invite, returned when requested with expiration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<FixedOffset>>,
+    ///extra information only returned by endpoints that require MANAGE_CHANNELS or MANAGE_GUILD
+    #[serde(flatten)]
+    pub metadata: Option<InviteMetadata>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InviteChannel {
+    ///the id of the channel
+    pub id: ChannelId,
+    ///the name of the channel
+    pub name: Option<String>,
+    ///the type of channel
+    #[serde(rename = "type")]
+    pub typ: ChannelType,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct InviteMetadata {
+    ///number of times this invite has been used
+    pub uses: u64,
+    ///max number of times this invite can be used, 0 for unlimited
+    pub max_uses: u64,
+    ///duration (in seconds) after which the invite expires, 0 for never
+    pub max_age: u64,
+    ///whether this invite only grants temporary membership
+    pub temporary: bool,
+    ///when this invite was created
+    pub created_at: DateTime<FixedOffset>,
+}
+
+enum_number!(InviteTargetType{
+    Stream = 1,
+    EmbeddedApplication = 2,
+});
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GuildTemplate {
+    ///the template code (unique ID)
+    pub code: String,
+    ///template name
+    pub name: String,
+    ///the description for the template
+    pub description: Option<String>,
+    ///number of times this template has been used
+    pub usage_count: u64,
+    ///the ID of the user who created the template
+    pub creator_id: UserId,
+    ///the user who created the template
+    pub creator: User,
+    ///when this template was created
+    pub created_at: DateTime<FixedOffset>,
+    ///when this template was last synced to the source guild
+    pub updated_at: DateTime<FixedOffset>,
+    ///the ID of the guild this template is based on
+    pub source_guild_id: GuildId,
+    ///the guild snapshot this template contains
+    pub serialized_source_guild: serde_json::Value,
+    ///whether the template has unsynced changes
+    pub is_dirty: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct User {
     //the user's id
@@ -359,7 +501,7 @@ pub struct PartialUser {
     pub email: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct PartialGuild {
     ///guild id
     pub id: GuildId,
@@ -375,7 +517,7 @@ pub struct PartialGuild {
     pub permissions: Option<Permissions>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct Guild {
     ///guild id
     pub id: GuildId,
@@ -449,6 +591,67 @@ pub struct Guild {
     pub presences: Option<Vec<PresenceUpdate>>,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct GuildPreview {
+    ///guild id
+    pub id: GuildId,
+    ///guild name (2-100 characters)
+    pub name: String,
+    ///icon hash
+    pub icon: Option<String>,
+    ///splash hash
+    pub splash: Option<String>,
+    ///discovery splash hash
+    pub discovery_splash: Option<String>,
+    ///custom guild emojis
+    pub emojis: Vec<Emoji>,
+    ///enabled guild features
+    pub features: Vec<String>,
+    ///approximate number of members in this guild
+    pub approximate_member_count: u64,
+    ///approximate number of online members in this guild
+    pub approximate_presence_count: u64,
+    ///the description for the guild, if the guild is discoverable
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GuildWidget {
+    ///whether the widget is enabled
+    pub enabled: bool,
+    ///the widget channel id
+    pub channel_id: Option<ChannelId>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VanityUrl {
+    ///the vanity invite code, if the guild has one
+    pub code: Option<String>,
+    ///how many times the vanity invite has been used
+    #[serde(default)]
+    pub uses: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WelcomeScreen {
+    ///the server description shown in the welcome screen
+    pub description: Option<String>,
+    ///the channels shown in the welcome screen, up to 5
+    pub welcome_channels: Vec<WelcomeScreenChannel>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WelcomeScreenChannel {
+    ///the channel's id
+    pub channel_id: ChannelId,
+    ///the description shown for the channel
+    pub description: String,
+    ///the emoji id, if the emoji is custom
+    pub emoji_id: Option<EmojiId>,
+    ///the emoji name if custom, the unicode character if standard, or null if no emoji is set
+    pub emoji_name: Option<String>,
+}
+
 bitflags! {
     pub struct Permissions: u64 {
         ///Allows creation of instant invites
@@ -523,6 +726,73 @@ impl<'de> serde::de::Deserialize<'de> for Permissions {
     }
 }
 
+impl Permissions {
+    ///A member's guild wide permissions, from the @everyone role and the member's roles
+    pub fn compute_base(guild: &Guild, member: &GuildMember) -> Permissions {
+        if guild.owner_id == member.user.id {
+            return Permissions::all();
+        }
+        //the @everyone role shares the guild's id
+        let everyone = guild
+            .roles
+            .iter()
+            .find(|role| role.id.0 == guild.id.0)
+            .map_or(Permissions::empty(), |role| role.permissions);
+        let permissions = guild
+            .roles
+            .iter()
+            .filter(|role| member.roles.contains(&role.id))
+            .fold(everyone, |permissions, role| permissions | role.permissions);
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            Permissions::all()
+        } else {
+            permissions
+        }
+    }
+
+    ///A member's permissions in a channel, applying the channel's overwrites to `base` (from `compute_base`)
+    ///
+    ///The @everyone overwrite is found using the channel's `guild_id`, so it's skipped if that isn't set
+    pub fn compute_overwrites(
+        base: Permissions,
+        channel: &Channel,
+        member: &GuildMember,
+    ) -> Permissions {
+        if base.contains(Permissions::ADMINISTRATOR) {
+            return Permissions::all();
+        }
+        let overwrites = &channel.permission_overwrites;
+        let mut permissions = base;
+
+        if let Some(guild_id) = channel.guild_id {
+            if let Some(everyone) = overwrites.iter().find(|overwrite| {
+                overwrite.typ == OverwriteType::Role && overwrite.id == guild_id.0
+            }) {
+                permissions = everyone.apply(permissions);
+            }
+        }
+
+        //role overwrites apply together, so an allow on any role beats a deny on another
+        let (allow, deny) = overwrites
+            .iter()
+            .filter(|overwrite| {
+                overwrite.typ == OverwriteType::Role && member.roles.contains(&RoleId(overwrite.id))
+            })
+            .fold((0, 0), |(allow, deny), overwrite| {
+                (allow | overwrite.allow, deny | overwrite.deny)
+            });
+        permissions.remove(Permissions::from_bits_truncate(deny));
+        permissions.insert(Permissions::from_bits_truncate(allow));
+
+        if let Some(member_overwrite) = overwrites.iter().find(|overwrite| {
+            overwrite.typ == OverwriteType::Member && overwrite.id == member.user.id.0
+        }) {
+            permissions = member_overwrite.apply(permissions);
+        }
+        permissions
+    }
+}
+
 impl serde::ser::Serialize for Permissions {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -597,7 +867,15 @@ pub struct GuildMember {
     pub mute: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct Ban {
+    ///the reason for the ban
+    pub reason: Option<String>,
+    ///the banned user
+    pub user: User,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct VoiceState {
     ///the guild id this voice state is for
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -623,7 +901,7 @@ pub struct VoiceState {
     pub suppress: bool,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct PresenceUpdate {
     ///the user presence is being updated for
     pub user: PartialUser,
@@ -638,7 +916,7 @@ pub struct PresenceUpdate {
     pub client_status: ClientStatus,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct ClientStatus {
     ///the user's status set for an active desktop (Windows, Linux, Mac) application session
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -750,6 +1028,61 @@ pub struct Reaction {
     pub emoji: Emoji,
 }
 
+///An emoji as used when reacting to a message
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReactionType {
+    ///a standard emoji, as its unicode character(s)
+    Unicode(String),
+    ///a guild's custom emoji
+    Custom {
+        id: EmojiId,
+        name: Option<String>,
+        animated: bool,
+    },
+}
+
+impl ReactionType {
+    ///The percent-encoded form of the emoji used in reaction endpoint paths
+    pub fn to_path_segment(&self) -> String {
+        use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+        match self {
+            ReactionType::Unicode(emoji) => {
+                utf8_percent_encode(emoji, PATH_SEGMENT_ENCODE_SET).to_string()
+            }
+            ReactionType::Custom { id, name, .. } => format!(
+                "{}:{}",
+                utf8_percent_encode(name.as_deref().unwrap_or("_"), PATH_SEGMENT_ENCODE_SET),
+                (id.0).0
+            ),
+        }
+    }
+}
+
+impl From<char> for ReactionType {
+    fn from(emoji: char) -> Self {
+        ReactionType::Unicode(emoji.to_string())
+    }
+}
+
+impl From<&str> for ReactionType {
+    fn from(emoji: &str) -> Self {
+        ReactionType::Unicode(emoji.to_owned())
+    }
+}
+
+impl From<Emoji> for ReactionType {
+    fn from(emoji: Emoji) -> Self {
+        match emoji.id {
+            Some(id) => ReactionType::Custom {
+                id,
+                name: emoji.name,
+                animated: emoji.animated.unwrap_or(false),
+            },
+            None => ReactionType::Unicode(emoji.name.unwrap_or_default()),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Attachment {
     ///attachment id
@@ -843,6 +1176,7 @@ bitflags! {
         const DIRECT_MESSAGES          = (1 << 12);
         const DIRECT_MESSAGE_REACTIONS = (1 << 13);
         const DIRECT_MESSAGE_TYPING	   = (1 << 14);
+        const GUILD_SCHEDULED_EVENTS   = (1 << 16);
 
         const KNOWN_PRIVILEGED = Self::GUILD_MEMBERS.bits | Self::GUILD_PRESENCES.bits;
     }
@@ -1012,6 +1346,50 @@ pub struct MessageStickerItem {
     pub format_type: u64,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct Sticker {
+    ///id of the sticker
+    pub id: StickerId,
+    ///for standard stickers, id of the pack the sticker is from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pack_id: Option<Snowflake>,
+    ///name of the sticker
+    pub name: String,
+    ///description of the sticker
+    pub description: Option<String>,
+    ///autocomplete/suggestion tags for the sticker (max 200 characters)
+    pub tags: String,
+    ///type of sticker
+    #[serde(rename = "type")]
+    pub typ: StickerType,
+    ///type of sticker format
+    pub format_type: StickerFormatType,
+    ///whether this guild sticker can be used, may be false due to loss of Server Boosts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available: Option<bool>,
+    ///id of the guild that owns this sticker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guild_id: Option<GuildId>,
+    ///the user that uploaded the guild sticker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    ///the standard sticker's sort order within its pack
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_value: Option<u64>,
+}
+
+enum_number!(StickerType{
+    Standard = 1,
+    Guild = 2,
+});
+
+enum_number!(StickerFormatType{
+    Png = 1,
+    Apng = 2,
+    Lottie = 3,
+    Gif = 4,
+});
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct ThreadMetadata {
     ///whether the thread is archived
@@ -1027,15 +1405,27 @@ pub struct ThreadMetadata {
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ThreadMember {
-    ///whether the thread is archived
-    pub archived: bool,
-    ///duration in minutes to automatically archive the thread after recent activity, can be set to: 60, 1440, 4320, 10080
-    pub auto_archive_duration: u64,
-    ///timestamp when the thread's archive status was last changed, used for calculating recent activity
-    pub archive_timestamp: DateTime<FixedOffset>,
-    ///when a thread is locked, only users with MANAGE_THREADS can unarchive it
+    ///the id of the thread (omitted within GUILD_CREATE)
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub locked: Option<bool>,
+    pub id: Option<ChannelId>,
+    ///the id of the user (omitted within GUILD_CREATE)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+    ///the time the current user last joined the thread
+    pub join_timestamp: DateTime<FixedOffset>,
+    ///any user-thread settings, currently only used for notifications
+    pub flags: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ThreadList {
+    ///the threads
+    pub threads: Vec<Channel>,
+    ///a thread member object for each returned thread the current user has joined
+    pub members: Vec<ThreadMember>,
+    ///whether there are potentially additional threads that could be returned on a subsequent call
+    #[serde(default)]
+    pub has_more: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -1394,6 +1784,78 @@ enum_number!(PrivacyLevel{
     GuildOnly = 2,
 });
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct GuildScheduledEvent {
+    ///the id of the scheduled event
+    pub id: ScheduledEventId,
+    ///the guild id which the scheduled event belongs to
+    pub guild_id: GuildId,
+    ///the channel id in which the scheduled event will be hosted, or None for external events
+    pub channel_id: Option<ChannelId>,
+    ///the id of the user that created the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator_id: Option<UserId>,
+    ///the name of the scheduled event (1-100 characters)
+    pub name: String,
+    ///the description of the scheduled event (1-1000 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    ///the time the scheduled event will start
+    pub scheduled_start_time: DateTime<FixedOffset>,
+    ///the time the scheduled event will end, required for external events
+    pub scheduled_end_time: Option<DateTime<FixedOffset>>,
+    ///the privacy level of the scheduled event
+    pub privacy_level: PrivacyLevel,
+    ///the status of the scheduled event
+    pub status: ScheduledEventStatus,
+    ///the type of the scheduled event
+    pub entity_type: ScheduledEventEntityType,
+    ///the id of an entity associated with the scheduled event
+    pub entity_id: Option<Snowflake>,
+    ///additional metadata for the scheduled event
+    pub entity_metadata: Option<ScheduledEventEntityMetadata>,
+    ///the user that created the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<User>,
+    ///the number of users subscribed to the scheduled event, returned when requested with the user count
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_count: Option<u64>,
+    ///the cover image hash of the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+enum_number!(ScheduledEventStatus{
+    Scheduled = 1,
+    Active = 2,
+    Completed = 3,
+    Canceled = 4,
+});
+
+enum_number!(ScheduledEventEntityType{
+    StageInstance = 1,
+    Voice = 2,
+    External = 3,
+});
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ScheduledEventEntityMetadata {
+    ///location of the event (1-100 characters), required for external events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GuildScheduledEventUser {
+    ///the scheduled event id which the user subscribed to
+    pub guild_scheduled_event_id: ScheduledEventId,
+    ///user which subscribed to an event
+    pub user: User,
+    ///guild member data for this user for the guild which this event belongs to, if requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<GuildMember>,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct InteractionResponse {
     ///the type of response
@@ -1524,3 +1986,114 @@ pub enum AllowedMentionTypes {
     Users,
     Everyone,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reaction_types_are_percent_encoded() {
+        assert_eq!(ReactionType::from('👍').to_path_segment(), "%F0%9F%91%8D");
+        let custom = ReactionType::Custom {
+            id: EmojiId(Snowflake(41771983429993937)),
+            name: Some("LUL".into()),
+            animated: false,
+        };
+        assert_eq!(custom.to_path_segment(), "LUL:41771983429993937");
+    }
+
+    #[test]
+    fn invite_metadata_is_only_present_when_returned() {
+        let public: Invite = serde_json::from_str(
+            r#"{"code":"0vCdhLbwjZZTWZLD","channel":{"id":"165176875973476352","name":"illuminati","type":0}}"#,
+        )
+        .unwrap();
+        assert!(public.metadata.is_none());
+
+        let managed: Invite = serde_json::from_str(
+            r#"{"code":"0vCdhLbwjZZTWZLD","channel":null,"uses":3,"max_uses":0,"max_age":0,"temporary":false,"created_at":"2016-03-31T19:15:39.954000+00:00"}"#,
+        )
+        .unwrap();
+        assert_eq!(managed.metadata.unwrap().uses, 3);
+    }
+
+    fn member(id: &str, roles: &[&str]) -> GuildMember {
+        serde_json::from_value(serde_json::json!({
+            "user": {"id": id, "username": "member", "discriminator": "0001", "avatar": null},
+            "roles": roles,
+            "joined_at": "2021-01-01T00:00:00+00:00",
+            "deaf": false,
+            "mute": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn base_permissions_combine_everyone_and_member_roles() {
+        let role = |id: &str, permissions: Permissions| {
+            serde_json::json!({
+                "id": id, "name": id, "color": 0, "hoist": false, "position": 0,
+                "permissions": permissions.bits().to_string(), "managed": false, "mentionable": false,
+            })
+        };
+        let guild: Guild = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "name": "guild",
+            "icon": null,
+            "splash": null,
+            "owner_id": "2",
+            "region": "europe",
+            "afk_channel_id": null,
+            "afk_timeout": 300,
+            "verification_level": 0,
+            "default_message_notifications": 0,
+            "explicit_content_filter": 0,
+            "roles": [
+                role("1", Permissions::VIEW_CHANNEL),
+                role("10", Permissions::SEND_MESSAGES),
+                role("11", Permissions::KICK_MEMBERS),
+                role("12", Permissions::ADMINISTRATOR),
+            ],
+            "emojis": [],
+            "features": [],
+            "mfa_level": 0,
+            "application_id": null,
+            "system_channel_id": null,
+        }))
+        .unwrap();
+
+        let base = |member: GuildMember| Permissions::compute_base(&guild, &member);
+        assert_eq!(base(member("2", &[])), Permissions::all());
+        assert_eq!(base(member("3", &[])), Permissions::VIEW_CHANNEL);
+        assert_eq!(
+            base(member("3", &["10"])),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+        );
+        assert_eq!(base(member("3", &["10", "12"])), Permissions::all());
+    }
+
+    #[test]
+    fn overwrites_apply_everyone_then_roles_then_member() {
+        let member: GuildMember = serde_json::from_str(
+            r#"{"user":{"id":"3","username":"mod","discriminator":"0001","avatar":null},"roles":["10","11"],"joined_at":"2021-01-01T00:00:00+00:00","deaf":false,"mute":false}"#,
+        )
+        .unwrap();
+        let channel: Channel = serde_json::from_str(
+            r#"{"id":"2","type":0,"guild_id":"1","permission_overwrites":[
+                {"id":"1","type":0,"allow":"0","deny":"3072"},
+                {"id":"10","type":0,"allow":"1024","deny":"0"},
+                {"id":"11","type":0,"allow":"0","deny":"1024"},
+                {"id":"3","type":1,"allow":"0","deny":"64"}
+            ]}"#,
+        )
+        .unwrap();
+        let base =
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::ADD_REACTIONS;
+
+        let permissions = Permissions::compute_overwrites(base, &channel, &member);
+        assert_eq!(permissions, Permissions::VIEW_CHANNEL);
+
+        let admin = Permissions::compute_overwrites(Permissions::ADMINISTRATOR, &channel, &member);
+        assert_eq!(admin, Permissions::all());
+    }
+}
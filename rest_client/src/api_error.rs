@@ -0,0 +1,219 @@
+use http::StatusCode;
+use serde::de::{Deserialize, Deserializer};
+use std::fmt;
+use thiserror::Error;
+
+///An error response from the discord api
+#[derive(Debug, Clone, Error)]
+#[error("Discord api returned {status}: {message} ({code})")]
+pub struct DiscordApiError {
+    pub status: StatusCode,
+    pub code: JsonErrorCode,
+    pub message: String,
+    ///per field errors, for requests rejected with `InvalidFormBody`. This is a nested map mirroring the
+    ///shape of the request body, where each invalid field has an `_errors` array of `{code, message}`
+    pub errors: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    code: JsonErrorCode,
+    message: String,
+    #[serde(default)]
+    errors: Option<serde_json::Value>,
+}
+
+impl DiscordApiError {
+    ///Parses an error response body, returning `None` if it isn't a discord error object
+    pub fn parse(status: StatusCode, body: &str) -> Option<Self> {
+        let body: ErrorBody = serde_json::from_str(body).ok()?;
+        Some(Self {
+            status,
+            code: body.code,
+            message: body.message,
+            errors: body.errors,
+        })
+    }
+}
+
+///The JSON error codes discord returns alongside a failed request
+///
+///Only common codes have their own variant, anything else is kept in `Other`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonErrorCode {
+    General,
+    UnknownAccount,
+    UnknownApplication,
+    UnknownChannel,
+    UnknownGuild,
+    UnknownIntegration,
+    UnknownInvite,
+    UnknownMember,
+    UnknownMessage,
+    UnknownOverwrite,
+    UnknownRole,
+    UnknownToken,
+    UnknownUser,
+    UnknownEmoji,
+    UnknownWebhook,
+    UnknownBan,
+    UnknownInteraction,
+    BotsCannotUseEndpoint,
+    OnlyBotsCanUseEndpoint,
+    MaxGuildsReached,
+    MaxPinsReached,
+    MaxRolesReached,
+    MaxWebhooksReached,
+    MaxReactionsReached,
+    Unauthorized,
+    RequestTooLarge,
+    MissingAccess,
+    InvalidAccountType,
+    CannotExecuteOnDmChannel,
+    CannotEditMessageByOtherUser,
+    CannotSendEmptyMessage,
+    CannotSendMessagesToUser,
+    CannotSendMessagesInVoiceChannel,
+    MissingPermissions,
+    InvalidAuthenticationToken,
+    MessageTooOldToBulkDelete,
+    InvalidFormBody,
+    ReactionBlocked,
+    ThreadArchived,
+    Other(u32),
+}
+
+impl JsonErrorCode {
+    pub fn code(self) -> u32 {
+        use JsonErrorCode::*;
+        match self {
+            General => 0,
+            UnknownAccount => 10001,
+            UnknownApplication => 10002,
+            UnknownChannel => 10003,
+            UnknownGuild => 10004,
+            UnknownIntegration => 10005,
+            UnknownInvite => 10006,
+            UnknownMember => 10007,
+            UnknownMessage => 10008,
+            UnknownOverwrite => 10009,
+            UnknownRole => 10011,
+            UnknownToken => 10012,
+            UnknownUser => 10013,
+            UnknownEmoji => 10014,
+            UnknownWebhook => 10015,
+            UnknownBan => 10026,
+            UnknownInteraction => 10062,
+            BotsCannotUseEndpoint => 20001,
+            OnlyBotsCanUseEndpoint => 20002,
+            MaxGuildsReached => 30001,
+            MaxPinsReached => 30003,
+            MaxRolesReached => 30005,
+            MaxWebhooksReached => 30007,
+            MaxReactionsReached => 30010,
+            Unauthorized => 40001,
+            RequestTooLarge => 40005,
+            MissingAccess => 50001,
+            InvalidAccountType => 50002,
+            CannotExecuteOnDmChannel => 50003,
+            CannotEditMessageByOtherUser => 50005,
+            CannotSendEmptyMessage => 50006,
+            CannotSendMessagesToUser => 50007,
+            CannotSendMessagesInVoiceChannel => 50008,
+            MissingPermissions => 50013,
+            InvalidAuthenticationToken => 50014,
+            MessageTooOldToBulkDelete => 50034,
+            InvalidFormBody => 50035,
+            ReactionBlocked => 90001,
+            ThreadArchived => 50083,
+            Other(code) => code,
+        }
+    }
+}
+
+impl From<u32> for JsonErrorCode {
+    fn from(code: u32) -> Self {
+        use JsonErrorCode::*;
+        match code {
+            0 => General,
+            10001 => UnknownAccount,
+            10002 => UnknownApplication,
+            10003 => UnknownChannel,
+            10004 => UnknownGuild,
+            10005 => UnknownIntegration,
+            10006 => UnknownInvite,
+            10007 => UnknownMember,
+            10008 => UnknownMessage,
+            10009 => UnknownOverwrite,
+            10011 => UnknownRole,
+            10012 => UnknownToken,
+            10013 => UnknownUser,
+            10014 => UnknownEmoji,
+            10015 => UnknownWebhook,
+            10026 => UnknownBan,
+            10062 => UnknownInteraction,
+            20001 => BotsCannotUseEndpoint,
+            20002 => OnlyBotsCanUseEndpoint,
+            30001 => MaxGuildsReached,
+            30003 => MaxPinsReached,
+            30005 => MaxRolesReached,
+            30007 => MaxWebhooksReached,
+            30010 => MaxReactionsReached,
+            40001 => Unauthorized,
+            40005 => RequestTooLarge,
+            50001 => MissingAccess,
+            50002 => InvalidAccountType,
+            50003 => CannotExecuteOnDmChannel,
+            50005 => CannotEditMessageByOtherUser,
+            50006 => CannotSendEmptyMessage,
+            50007 => CannotSendMessagesToUser,
+            50008 => CannotSendMessagesInVoiceChannel,
+            50013 => MissingPermissions,
+            50014 => InvalidAuthenticationToken,
+            50034 => MessageTooOldToBulkDelete,
+            50035 => InvalidFormBody,
+            50083 => ThreadArchived,
+            90001 => ReactionBlocked,
+            other => Other(other),
+        }
+    }
+}
+
+impl fmt::Display for JsonErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonErrorCode::Other(code) => write!(f, "code {}", code),
+            known => write!(f, "{:?}, code {}", known, known.code()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonErrorCode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(u32::deserialize(deserializer)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_error_body() {
+        let body = r#"{"code": 50035, "errors": {"content": {"_errors": [{"code": "BASE_TYPE_MAX_LENGTH", "message": "Must be 2000 or fewer in length."}]}}, "message": "Invalid Form Body"}"#;
+        let error = DiscordApiError::parse(StatusCode::BAD_REQUEST, body).unwrap();
+        assert_eq!(error.code, JsonErrorCode::InvalidFormBody);
+        assert_eq!(error.message, "Invalid Form Body");
+        assert!(error.errors.is_some());
+
+        let error =
+            DiscordApiError::parse(StatusCode::NOT_FOUND, r#"{"code": 12345, "message": "?"}"#)
+                .unwrap();
+        assert_eq!(error.code, JsonErrorCode::Other(12345));
+
+        assert!(DiscordApiError::parse(StatusCode::BAD_GATEWAY, "<html></html>").is_none());
+    }
+}
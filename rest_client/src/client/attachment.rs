@@ -0,0 +1,208 @@
+use crate::Error;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::Serialize;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+///A file to upload alongside a message
+///
+///The contents are held in memory so the request can be retried if it is rate limited.
+///Embeds can show an attached image by using `attachment.url()` (`attachment://filename`) as the image url.
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub filename: String,
+    pub data: Vec<u8>,
+}
+
+impl Attachment {
+    pub fn bytes<S: Into<String>, D: Into<Vec<u8>>>(filename: S, data: D) -> Self {
+        Self {
+            filename: filename.into(),
+            data: data.into(),
+        }
+    }
+
+    ///Reads a file from disk, using its file name as the attachment name
+    pub async fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let filename = path
+            .file_name()
+            .map(|filename| filename.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "file".into());
+        let data = tokio::fs::read(path).await?;
+        Ok(Self { filename, data })
+    }
+
+    ///Reads the reader to the end, buffering its contents
+    pub async fn from_reader<S, R>(filename: S, mut reader: R) -> Result<Self, Error>
+    where
+        S: Into<String>,
+        R: AsyncRead + Unpin,
+    {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data).await?;
+        Ok(Self {
+            filename: filename.into(),
+            data,
+        })
+    }
+
+    ///The url to reference this attachment from within an embed
+    pub fn url(&self) -> String {
+        format!("attachment://{}", self.filename)
+    }
+}
+
+///Encodes an image as the data uri discord expects for icons, avatars and emojis
+///
+///The image format is detected from its contents, png, jpeg, gif and webp images are supported
+pub fn image_data_uri(data: &[u8]) -> Result<String, Error> {
+    let mime = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        "image/gif"
+    } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        "image/webp"
+    } else {
+        return Err(Error::UnknownImageFormat);
+    };
+    Ok(format!("data:{};base64,{}", mime, base64::encode(data)))
+}
+
+///A `multipart/form-data` request body
+pub(crate) struct Multipart {
+    pub boundary: String,
+    pub body: Vec<u8>,
+}
+
+impl Multipart {
+    ///A `payload_json` part followed by one part per file
+    pub fn new<T: Serialize>(payload: &T, files: &[Attachment]) -> Result<Self, Error> {
+        let mut multipart = Self::empty();
+        multipart.part(
+            "Content-Disposition: form-data; name=\"payload_json\"",
+            "application/json",
+            &serde_json::to_vec(payload)?,
+        );
+        for (i, file) in files.iter().enumerate() {
+            multipart.file(&format!("files[{}]", i), file);
+        }
+        multipart.finish();
+        Ok(multipart)
+    }
+
+    ///Plain text fields followed by a single file, for endpoints that don't accept `payload_json`
+    pub fn form(fields: &[(&str, &str)], name: &str, file: &Attachment) -> Self {
+        let mut multipart = Self::empty();
+        for (field, value) in fields {
+            multipart.part(
+                &format!("Content-Disposition: form-data; name=\"{}\"", field),
+                "text/plain; charset=utf-8",
+                value.as_bytes(),
+            );
+        }
+        multipart.file(name, file);
+        multipart.finish();
+        multipart
+    }
+
+    fn empty() -> Self {
+        let boundary = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .collect();
+        Self {
+            boundary,
+            body: Vec::new(),
+        }
+    }
+
+    fn file(&mut self, name: &str, file: &Attachment) {
+        self.part(
+            &format!(
+                "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"",
+                name,
+                escape_filename(&file.filename)
+            ),
+            "application/octet-stream",
+            &file.data,
+        );
+    }
+
+    fn part(&mut self, disposition: &str, content_type: &str, data: &[u8]) {
+        self.body
+            .extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+        self.body
+            .extend_from_slice(format!("{}\r\n", disposition).as_bytes());
+        self.body
+            .extend_from_slice(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    fn finish(&mut self) {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+    }
+
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+}
+
+///Percent-encodes the characters that would end the quoted filename or the header line,
+///the same way browsers encode filenames in form submissions
+fn escape_filename(filename: &str) -> String {
+    filename
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_data_uri_detects_format() {
+        let png = b"\x89PNG\r\n\x1a\nrest";
+        assert_eq!(
+            image_data_uri(png).unwrap(),
+            "data:image/png;base64,iVBORw0KGgpyZXN0"
+        );
+        assert!(image_data_uri(b"not an image").is_err());
+    }
+
+    #[test]
+    fn multipart_body_has_payload_and_file_parts() {
+        let files = [
+            Attachment::bytes("a.txt", "first"),
+            Attachment::bytes("evil\"\r\nX-Injected: 1.txt", "second"),
+        ];
+        let multipart = Multipart::new(&serde_json::json!({"content": "hi"}), &files).unwrap();
+        let boundary = &multipart.boundary;
+        let expected = format!(
+            "--{b}\r\n\
+             Content-Disposition: form-data; name=\"payload_json\"\r\n\
+             Content-Type: application/json\r\n\r\n\
+             {{\"content\":\"hi\"}}\r\n\
+             --{b}\r\n\
+             Content-Disposition: form-data; name=\"files[0]\"; filename=\"a.txt\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n\
+             first\r\n\
+             --{b}\r\n\
+             Content-Disposition: form-data; name=\"files[1]\"; filename=\"evil%22%0D%0AX-Injected: 1.txt\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n\
+             second\r\n\
+             --{b}--\r\n",
+            b = boundary
+        );
+        assert_eq!(String::from_utf8(multipart.body.clone()).unwrap(), expected);
+        assert_eq!(
+            multipart.content_type(),
+            format!("multipart/form-data; boundary={}", boundary)
+        );
+    }
+}
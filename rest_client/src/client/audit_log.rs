@@ -0,0 +1,75 @@
+use super::paginate::{next_cursor, paginate};
+use super::Client;
+use crate::model::*;
+use crate::Error;
+use futures::Stream;
+
+///Filters for `Client::get_audit_log`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GetAuditLog {
+    ///only entries from actions made by this user
+    pub user_id: Option<UserId>,
+    ///only entries of this type
+    pub action_type: Option<AuditLogEvent>,
+    ///only entries before this entry
+    pub before: Option<AuditLogEntryId>,
+    ///how many entries to return (1-100), 50 if not set
+    pub limit: Option<u64>,
+}
+
+impl GetAuditLog {
+    fn to_query(self) -> String {
+        let mut query = Vec::new();
+        if let Some(user_id) = self.user_id {
+            query.push(format!("user_id={}", (user_id.0).0));
+        }
+        if let Some(action_type) = self.action_type {
+            query.push(format!("action_type={}", action_type.value()));
+        }
+        if let Some(before) = self.before {
+            query.push(format!("before={}", (before.0).0));
+        }
+        if let Some(limit) = self.limit {
+            query.push(format!("limit={}", limit));
+        }
+        query.join("&")
+    }
+}
+
+impl Client {
+    pub async fn get_audit_log(
+        &self,
+        guild_id: GuildId,
+        filter: GetAuditLog,
+    ) -> Result<AuditLog, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/audit-logs?{query}",
+            guild_id = (guild_id.0).0,
+            query = filter.to_query()
+        );
+        self.get_json(url).await
+    }
+
+    ///All audit log entries matching `filter`, newest first, fetching further pages as needed
+    pub fn audit_log_entries(
+        &self,
+        guild_id: GuildId,
+        filter: GetAuditLog,
+    ) -> impl Stream<Item = Result<AuditLogEntry, Error>> + Send + 'static {
+        let client = self.clone();
+        paginate(filter, move |mut filter| {
+            let client = client.clone();
+            async move {
+                let entries = client
+                    .get_audit_log(guild_id, filter)
+                    .await?
+                    .audit_log_entries;
+                let next = next_cursor(&entries, filter.limit.unwrap_or(50), |entry| {
+                    filter.before = Some(entry.id);
+                    filter
+                });
+                Ok((entries, next))
+            }
+        })
+    }
+}
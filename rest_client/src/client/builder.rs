@@ -0,0 +1,204 @@
+use super::{Client, RateLimitCallback, RateLimitHit};
+use crate::{Error, DEFAULT_API_VERSION, DEFAULT_BASE_URL};
+use std::sync::Arc;
+use std::time::Duration;
+
+const USER_AGENT: &str = "discord-next-rs (github.com/Eroc33, 0.0.1-prototype)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    ///a bot token, as used by bot users
+    Bot,
+    ///an OAuth2 bearer token, for acting on behalf of a user
+    Bearer,
+}
+
+impl TokenType {
+    fn prefix(self) -> &'static str {
+        match self {
+            TokenType::Bot => "Bot",
+            TokenType::Bearer => "Bearer",
+        }
+    }
+}
+
+pub struct ClientBuilder {
+    token: Option<String>,
+    token_type: TokenType,
+    base_url: String,
+    api_version: u8,
+    http_client: Option<reqwest::Client>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent_suffix: Option<String>,
+    max_retries: u16,
+    on_rate_limit: Option<RateLimitCallback>,
+}
+
+impl ClientBuilder {
+    pub fn new<S: Into<String>>(token: S) -> Self {
+        Self::with_token(Some(token.into()))
+    }
+
+    ///A client that doesn't send an Authorization header, for endpoints authorized by a token in the url (e.g. webhook execution)
+    pub fn unauthenticated() -> Self {
+        Self::with_token(None)
+    }
+
+    pub(crate) fn without_token(mut self) -> Self {
+        self.token = None;
+        self
+    }
+
+    fn with_token(token: Option<String>) -> Self {
+        Self {
+            token,
+            token_type: TokenType::Bot,
+            base_url: DEFAULT_BASE_URL.into(),
+            api_version: DEFAULT_API_VERSION,
+            http_client: None,
+            timeout: None,
+            connect_timeout: None,
+            user_agent_suffix: None,
+            max_retries: 5,
+            on_rate_limit: None,
+        }
+    }
+
+    pub fn token_type(mut self, token_type: TokenType) -> Self {
+        self.token_type = token_type;
+        self
+    }
+
+    ///the base url requests are made against, without the version (e.g. `https://discord.com/api`)
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn api_version(mut self, api_version: u8) -> Self {
+        self.api_version = api_version;
+        self
+    }
+
+    ///use a preconfigured http client, e.g. one set up to use a proxy
+    pub fn http_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    ///timeout for each individual request, including reading the response body
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    ///timeout for establishing connections. Ignored if a custom http client is used
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    ///appended to the library's user agent
+    pub fn user_agent_suffix<S: Into<String>>(mut self, suffix: S) -> Self {
+        self.user_agent_suffix = Some(suffix.into());
+        self
+    }
+
+    ///how many times a request is retried after being rate limited or failing with a server error
+    pub fn max_retries(mut self, max_retries: u16) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    ///called whenever a request is rejected with a 429
+    pub fn on_rate_limit<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&RateLimitHit) + Send + Sync + 'static,
+    {
+        self.on_rate_limit = Some(Arc::new(f));
+        self
+    }
+
+    pub fn build(self) -> Result<Client, Error> {
+        let http_client = match self.http_client {
+            Some(http_client) => http_client,
+            None => {
+                let mut builder = reqwest::Client::builder();
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                builder.build()?
+            }
+        };
+        let user_agent = match self.user_agent_suffix {
+            Some(suffix) => format!("{} {}", USER_AGENT, suffix),
+            None => USER_AGENT.into(),
+        };
+        let token_type = self.token_type;
+        Ok(Client {
+            http_client,
+            authorization: self
+                .token
+                .map(|token| format!("{} {}", token_type.prefix(), token)),
+            api_base: format!(
+                "{}/v{}",
+                self.base_url.trim_end_matches('/'),
+                self.api_version
+            ),
+            user_agent,
+            timeout: self.timeout,
+            rate_limiter: Default::default(),
+            on_rate_limit: self.on_rate_limit,
+            max_retries: self.max_retries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_prefixed_with_their_type() {
+        let client = ClientBuilder::new("abc").build().unwrap();
+        assert_eq!(client.authorization.as_deref(), Some("Bot abc"));
+
+        let client = ClientBuilder::new("abc")
+            .token_type(TokenType::Bearer)
+            .build()
+            .unwrap();
+        assert_eq!(client.authorization.as_deref(), Some("Bearer abc"));
+
+        let client = ClientBuilder::unauthenticated().build().unwrap();
+        assert_eq!(client.authorization, None);
+    }
+
+    #[test]
+    fn api_base_includes_the_version() {
+        let client = ClientBuilder::new("abc").build().unwrap();
+        assert_eq!(
+            client.api_base,
+            format!("https://discord.com/api/v{}", DEFAULT_API_VERSION)
+        );
+
+        let client = ClientBuilder::new("abc")
+            .base_url("http://localhost:8080/api/")
+            .api_version(8)
+            .build()
+            .unwrap();
+        assert_eq!(client.api_base, "http://localhost:8080/api/v8");
+    }
+
+    #[test]
+    fn user_agent_suffix_is_appended() {
+        let client = ClientBuilder::new("abc").build().unwrap();
+        assert_eq!(client.user_agent, USER_AGENT);
+
+        let client = ClientBuilder::new("abc")
+            .user_agent_suffix("mybot/1.0")
+            .build()
+            .unwrap();
+        assert_eq!(client.user_agent, format!("{} mybot/1.0", USER_AGENT));
+    }
+}
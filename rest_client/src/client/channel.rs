@@ -0,0 +1,157 @@
+use super::{Client, RequestBody};
+use crate::model::*;
+use crate::Error;
+use futures::{channel::oneshot, select, FutureExt};
+use std::time::Duration;
+
+use tracing::*;
+
+///how often typing is re-triggered, discord shows the indicator for 10 seconds
+const TYPING_INTERVAL: Duration = Duration::from_secs(8);
+
+#[derive(Serialize)]
+pub struct EditOverwrite {
+    ///the bitwise value of all allowed permissions
+    pub allow: Permissions,
+    ///the bitwise value of all disallowed permissions
+    pub deny: Permissions,
+    ///whether the overwrite is for a role or a member
+    #[serde(rename = "type")]
+    pub typ: OverwriteType,
+}
+
+///Keeps the typing indicator shown in a channel until dropped
+pub struct Typing {
+    _stop: oneshot::Sender<()>,
+}
+
+impl Client {
+    pub async fn get_pinned_messages(&self, channel_id: ChannelId) -> Result<Vec<Message>, Error> {
+        let url = format!("/channels/{channel_id}/pins", channel_id = (channel_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn pin_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/pins/{message_id}",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0
+        );
+        self.put(url, reason).await
+    }
+
+    pub async fn unpin_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/pins/{message_id}",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+
+    ///Shows the typing indicator for the bot for 10 seconds, or until it sends a message
+    pub async fn trigger_typing_indicator(&self, channel_id: ChannelId) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/typing",
+            channel_id = (channel_id.0).0
+        );
+        self.execute_request(reqwest::Method::POST, url, RequestBody::Empty, None)
+            .await?;
+        Ok(())
+    }
+
+    ///Keeps re-triggering the typing indicator in the background until the returned guard is dropped
+    pub fn typing(&self, channel_id: ChannelId) -> Typing {
+        let (stop_sender, stop) = oneshot::channel::<()>();
+        let client = self.clone();
+        tokio::spawn(async move {
+            let mut stop = stop.fuse();
+            loop {
+                if let Err(e) = client.trigger_typing_indicator(channel_id).await {
+                    warn!("Couldn't trigger typing in {:?}: {:?}", channel_id, e);
+                }
+                let mut wait = tokio::time::delay_for(TYPING_INTERVAL).fuse();
+                select! {
+                    _ = stop => break,
+                    _ = wait => {}
+                }
+            }
+        });
+        Typing { _stop: stop_sender }
+    }
+
+    ///Publishes a message in a news channel to the channels following it
+    pub async fn crosspost_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<Message, Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/crosspost",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0
+        );
+        let res = self
+            .execute_request(reqwest::Method::POST, url, RequestBody::Empty, None)
+            .await?;
+        Ok(res.json().await?)
+    }
+
+    ///Follows a news channel, sending its crossposted messages to `webhook_channel_id`
+    pub async fn follow_news_channel(
+        &self,
+        channel_id: ChannelId,
+        webhook_channel_id: ChannelId,
+    ) -> Result<FollowedChannel, Error> {
+        let url = format!(
+            "/channels/{channel_id}/followers",
+            channel_id = (channel_id.0).0
+        );
+        self.post_return_json(
+            url,
+            json!({ "webhook_channel_id": webhook_channel_id }),
+            None,
+        )
+        .await
+    }
+
+    ///Creates or replaces the permission overwrite for a role or member
+    pub async fn edit_channel_permissions(
+        &self,
+        channel_id: ChannelId,
+        overwrite_id: Snowflake,
+        overwrite: EditOverwrite,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/permissions/{overwrite_id}",
+            channel_id = (channel_id.0).0,
+            overwrite_id = overwrite_id.0
+        );
+        self.put_json(url, overwrite, reason).await
+    }
+
+    pub async fn delete_channel_permission(
+        &self,
+        channel_id: ChannelId,
+        overwrite_id: Snowflake,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/permissions/{overwrite_id}",
+            channel_id = (channel_id.0).0,
+            overwrite_id = overwrite_id.0
+        );
+        self.delete(url, reason).await
+    }
+}
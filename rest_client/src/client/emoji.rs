@@ -0,0 +1,84 @@
+use super::Client;
+use crate::model::*;
+use crate::Error;
+
+#[derive(Serialize)]
+pub struct NewEmoji {
+    ///name of the emoji
+    pub name: String,
+    ///the 128x128 emoji image, as a data uri (see `image_data_uri`)
+    pub image: String,
+    ///roles allowed to use this emoji
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<RoleId>,
+}
+
+#[derive(Serialize, Default)]
+pub struct EditEmoji {
+    ///name of the emoji
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    ///roles allowed to use this emoji, an empty list allows everyone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<RoleId>>,
+}
+
+impl Client {
+    pub async fn list_guild_emojis(&self, guild_id: GuildId) -> Result<Vec<Emoji>, Error> {
+        let url = format!("/guilds/{guild_id}/emojis", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn get_guild_emoji(
+        &self,
+        guild_id: GuildId,
+        emoji_id: EmojiId,
+    ) -> Result<Emoji, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/emojis/{emoji_id}",
+            guild_id = (guild_id.0).0,
+            emoji_id = (emoji_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    ///Requires MANAGE_EMOJIS_AND_STICKERS. Emojis and animated emojis have a maximum file size of 256kb
+    pub async fn create_guild_emoji(
+        &self,
+        guild_id: GuildId,
+        emoji: NewEmoji,
+        reason: Option<&str>,
+    ) -> Result<Emoji, Error> {
+        let url = format!("/guilds/{guild_id}/emojis", guild_id = (guild_id.0).0);
+        self.post_return_json(url, emoji, reason).await
+    }
+
+    pub async fn modify_guild_emoji(
+        &self,
+        guild_id: GuildId,
+        emoji_id: EmojiId,
+        emoji: EditEmoji,
+        reason: Option<&str>,
+    ) -> Result<Emoji, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/emojis/{emoji_id}",
+            guild_id = (guild_id.0).0,
+            emoji_id = (emoji_id.0).0
+        );
+        self.patch_return_json(url, emoji, reason).await
+    }
+
+    pub async fn delete_guild_emoji(
+        &self,
+        guild_id: GuildId,
+        emoji_id: EmojiId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/emojis/{emoji_id}",
+            guild_id = (guild_id.0).0,
+            emoji_id = (emoji_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+}
@@ -0,0 +1,353 @@
+use super::Client;
+use crate::model::*;
+use crate::Error;
+use itertools::Itertools;
+
+#[derive(Default, Serialize)]
+pub struct EditGuild {
+    ///guild name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    ///guild voice region id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    ///verification level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_level: Option<u64>,
+    ///default message notification level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_message_notifications: Option<u64>,
+    ///explicit content filter level
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explicit_content_filter: Option<u64>,
+    ///id for afk channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub afk_channel_id: Option<ChannelId>,
+    ///afk timeout in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub afk_timeout: Option<u64>,
+    ///base64 1024x1024 png/jpeg/gif image for the guild icon, as a data uri (see `image_data_uri`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    ///user id to transfer guild ownership to (must be owner)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<UserId>,
+    ///base64 16:9 png/jpeg image for the guild splash, as a data uri (see `image_data_uri`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub splash: Option<String>,
+    ///the id of the channel where guild notices such as welcome messages and boost events are posted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_channel_id: Option<ChannelId>,
+    ///the id of the channel where guilds with the "PUBLIC" feature display rules and/or guidelines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules_channel_id: Option<ChannelId>,
+    ///the id of the channel where admins and moderators of guilds with the "PUBLIC" feature receive notices from discord
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_updates_channel_id: Option<ChannelId>,
+    ///the preferred locale of a guild with the "PUBLIC" feature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_locale: Option<String>,
+    ///the description for the guild, if the guild is discoverable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct NewChannel {
+    ///channel name (2-100 characters)
+    pub name: String,
+    ///the type of channel
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<ChannelType>,
+    ///channel topic (0-1024 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    ///the bitrate (in bits) of the voice channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u64>,
+    ///the user limit of the voice channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u64>,
+    ///amount of seconds a user has to wait before sending another message (0-21600)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u64>,
+    ///sorting position of the channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+    ///the channel's permission overwrites
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permission_overwrites: Vec<Overwrite>,
+    ///id of the parent category for a channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<ChannelId>,
+    ///whether the channel is nsfw
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+}
+
+impl NewChannel {
+    pub fn new<S: Into<String>>(name: S, typ: ChannelType) -> Self {
+        Self {
+            name: name.into(),
+            typ: Some(typ),
+            topic: None,
+            bitrate: None,
+            user_limit: None,
+            rate_limit_per_user: None,
+            position: None,
+            permission_overwrites: Vec::new(),
+            parent_id: None,
+            nsfw: None,
+        }
+    }
+}
+
+#[derive(Default, Serialize)]
+pub struct EditChannel {
+    ///channel name (2-100 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    ///the type of channel, only conversion between text and news is supported
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<ChannelType>,
+    ///sorting position of the channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+    ///channel topic (0-1024 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    ///whether the channel is nsfw
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+    ///amount of seconds a user has to wait before sending another message (0-21600)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u64>,
+    ///the bitrate (in bits) of the voice channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<u64>,
+    ///the user limit of the voice channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u64>,
+    ///the channel's permission overwrites, replacing all existing overwrites
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_overwrites: Option<Vec<Overwrite>>,
+    ///id of the parent category for a channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<ChannelId>,
+    ///voice region id for the voice channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rtc_region: Option<String>,
+    ///the camera video quality mode of the voice channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video_quality_mode: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct ChannelPosition {
+    ///channel id
+    pub id: ChannelId,
+    ///sorting position of the channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u64>,
+    ///syncs the permission overwrites with the new parent, if moving to a new category
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_permissions: Option<bool>,
+    ///the new parent id for the channel that is moved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<ChannelId>,
+}
+
+#[derive(Default, Serialize)]
+pub struct BeginPrune {
+    ///number of days to prune (1-30), 7 if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub days: Option<u64>,
+    ///whether the number of pruned members is returned, discouraged for large guilds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_prune_count: Option<bool>,
+    ///roles to include, by default members with roles are not pruned
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_roles: Vec<RoleId>,
+}
+
+#[derive(Default, Serialize)]
+pub struct EditGuildWidget {
+    ///whether the widget is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    ///the widget channel id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+}
+
+#[derive(Default, Serialize)]
+pub struct EditWelcomeScreen {
+    ///whether the welcome screen is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    ///channels linked in the welcome screen and their display options
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub welcome_channels: Option<Vec<WelcomeScreenChannel>>,
+    ///the server description to show in the welcome screen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PruneCount {
+    pruned: Option<u64>,
+}
+
+impl Client {
+    pub async fn get_guild(&self, guild_id: GuildId, with_counts: bool) -> Result<Guild, Error> {
+        let url = format!(
+            "/guilds/{guild_id}?with_counts={with_counts}",
+            guild_id = (guild_id.0).0,
+            with_counts = with_counts
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn modify_guild(
+        &self,
+        guild_id: GuildId,
+        edit: EditGuild,
+        reason: Option<&str>,
+    ) -> Result<Guild, Error> {
+        let url = format!("/guilds/{guild_id}", guild_id = (guild_id.0).0);
+        self.patch_return_json(url, edit, reason).await
+    }
+
+    pub async fn get_guild_preview(&self, guild_id: GuildId) -> Result<GuildPreview, Error> {
+        let url = format!("/guilds/{guild_id}/preview", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn create_guild_channel(
+        &self,
+        guild_id: GuildId,
+        channel: NewChannel,
+        reason: Option<&str>,
+    ) -> Result<Channel, Error> {
+        let url = format!("/guilds/{guild_id}/channels", guild_id = (guild_id.0).0);
+        self.post_return_json(url, channel, reason).await
+    }
+
+    ///Moves channels within a guild, only the channels being moved need to be included
+    pub async fn reorder_guild_channels(
+        &self,
+        guild_id: GuildId,
+        positions: Vec<ChannelPosition>,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!("/guilds/{guild_id}/channels", guild_id = (guild_id.0).0);
+        self.patch_json(url, positions, reason).await
+    }
+
+    pub async fn get_channel(&self, channel_id: ChannelId) -> Result<Channel, Error> {
+        let url = format!("/channels/{channel_id}", channel_id = (channel_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn modify_channel(
+        &self,
+        channel_id: ChannelId,
+        edit: EditChannel,
+        reason: Option<&str>,
+    ) -> Result<Channel, Error> {
+        let url = format!("/channels/{channel_id}", channel_id = (channel_id.0).0);
+        self.patch_return_json(url, edit, reason).await
+    }
+
+    ///Deletes a guild channel, or closes a private message. Returns the deleted channel
+    pub async fn delete_channel(
+        &self,
+        channel_id: ChannelId,
+        reason: Option<&str>,
+    ) -> Result<Channel, Error> {
+        let url = format!("/channels/{channel_id}", channel_id = (channel_id.0).0);
+        self.delete_return_json(url, reason).await
+    }
+
+    ///The number of members that would be removed by a prune of `days` days (7 if `None`)
+    pub async fn get_guild_prune_count(
+        &self,
+        guild_id: GuildId,
+        days: Option<u64>,
+        include_roles: &[RoleId],
+    ) -> Result<u64, Error> {
+        let mut query = Vec::new();
+        if let Some(days) = days {
+            query.push(format!("days={}", days));
+        }
+        if !include_roles.is_empty() {
+            query.push(format!(
+                "include_roles={}",
+                include_roles.iter().map(|role| (role.0).0).join(",")
+            ));
+        }
+        let url = format!(
+            "/guilds/{guild_id}/prune?{query}",
+            guild_id = (guild_id.0).0,
+            query = query.join("&")
+        );
+        let count: PruneCount = self.get_json(url).await?;
+        Ok(count.pruned.unwrap_or(0))
+    }
+
+    ///Kicks inactive members. Returns the number pruned if `compute_prune_count` was set
+    pub async fn begin_guild_prune(
+        &self,
+        guild_id: GuildId,
+        prune: BeginPrune,
+        reason: Option<&str>,
+    ) -> Result<Option<u64>, Error> {
+        let url = format!("/guilds/{guild_id}/prune", guild_id = (guild_id.0).0);
+        let count: PruneCount = self.post_return_json(url, prune, reason).await?;
+        Ok(count.pruned)
+    }
+
+    pub async fn get_guild_vanity_url(&self, guild_id: GuildId) -> Result<VanityUrl, Error> {
+        let url = format!("/guilds/{guild_id}/vanity-url", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn get_guild_widget_settings(&self, guild_id: GuildId) -> Result<GuildWidget, Error> {
+        let url = format!("/guilds/{guild_id}/widget", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn modify_guild_widget(
+        &self,
+        guild_id: GuildId,
+        edit: EditGuildWidget,
+        reason: Option<&str>,
+    ) -> Result<GuildWidget, Error> {
+        let url = format!("/guilds/{guild_id}/widget", guild_id = (guild_id.0).0);
+        self.patch_return_json(url, edit, reason).await
+    }
+
+    pub async fn get_guild_welcome_screen(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<WelcomeScreen, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/welcome-screen",
+            guild_id = (guild_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn modify_guild_welcome_screen(
+        &self,
+        guild_id: GuildId,
+        edit: EditWelcomeScreen,
+        reason: Option<&str>,
+    ) -> Result<WelcomeScreen, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/welcome-screen",
+            guild_id = (guild_id.0).0
+        );
+        self.patch_return_json(url, edit, reason).await
+    }
+}
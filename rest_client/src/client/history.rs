@@ -0,0 +1,216 @@
+use super::paginate::{next_cursor, paginate};
+use super::{Client, GetMessages};
+use crate::model::*;
+use crate::Error;
+use chrono::{DateTime, FixedOffset};
+use futures::Stream;
+use std::cmp::Reverse;
+
+const PAGE_SIZE: u32 = 100;
+
+///Where `Client::message_history_until` stops, the bound itself isn't included
+#[derive(Debug, Clone, Copy)]
+pub enum StopAt {
+    ///stop on reaching this message
+    Message(MessageId),
+    ///stop on reaching messages sent before (or after, when walking forwards) this time
+    Timestamp(DateTime<FixedOffset>),
+}
+
+impl StopAt {
+    fn reached(&self, message: &Message, forwards: bool) -> bool {
+        match (self, forwards) {
+            (StopAt::Message(id), true) => message.id >= *id,
+            (StopAt::Message(id), false) => message.id <= *id,
+            (StopAt::Timestamp(time), true) => message.timestamp > *time,
+            (StopAt::Timestamp(time), false) => message.timestamp < *time,
+        }
+    }
+}
+
+impl Client {
+    ///A channel's messages starting from `position`, fetching further pages as needed
+    ///
+    ///`MostRecent` and `Before` walk backwards through history, newest first. `After` walks forwards, oldest first.
+    ///`Around` only yields the single page around that message.
+    pub fn message_history(
+        &self,
+        channel_id: ChannelId,
+        position: GetMessages,
+    ) -> impl Stream<Item = Result<Message, Error>> + Send + 'static {
+        self.history(channel_id, position, None)
+    }
+
+    ///Like `message_history`, but ends once `stop` is reached
+    pub fn message_history_until(
+        &self,
+        channel_id: ChannelId,
+        position: GetMessages,
+        stop: StopAt,
+    ) -> impl Stream<Item = Result<Message, Error>> + Send + 'static {
+        self.history(channel_id, position, Some(stop))
+    }
+
+    pub(crate) fn history(
+        &self,
+        channel_id: ChannelId,
+        position: GetMessages,
+        stop: Option<StopAt>,
+    ) -> impl Stream<Item = Result<Message, Error>> + Send + 'static {
+        let client = self.clone();
+        paginate(position, move |position| {
+            let client = client.clone();
+            async move {
+                let messages = client
+                    .get_messages(channel_id, position, Some(PAGE_SIZE))
+                    .await?;
+                Ok(process_page(messages, position, stop))
+            }
+        })
+    }
+}
+
+///Orders a fetched page in walking direction, and works out the cursor for the next page.
+///Cuts the page short and ends the walk if it reaches `stop`
+fn process_page(
+    mut messages: Vec<Message>,
+    position: GetMessages,
+    stop: Option<StopAt>,
+) -> (Vec<Message>, Option<GetMessages>) {
+    let forwards = matches!(position, GetMessages::After(_));
+    //put each page in the order we're walking, rather than relying on the order discord returns
+    if forwards {
+        messages.sort_by_key(|message| message.id);
+    } else {
+        messages.sort_by_key(|message| Reverse(message.id));
+    }
+    let mut next = match position {
+        GetMessages::Around(_) => None,
+        GetMessages::After(_) => next_cursor(&messages, PAGE_SIZE.into(), |message| {
+            GetMessages::After(message.id)
+        }),
+        GetMessages::Before(_) | GetMessages::MostRecent => {
+            next_cursor(&messages, PAGE_SIZE.into(), |message| {
+                GetMessages::Before(message.id)
+            })
+        }
+    };
+    if let Some(stop) = stop {
+        if let Some(end) = messages
+            .iter()
+            .position(|message| stop.reached(message, forwards))
+        {
+            messages.truncate(end);
+            next = None;
+        }
+    }
+    (messages, next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///a full page of messages 1..=100, sent one minute apart, in the order discord returns them
+    fn page() -> Vec<Message> {
+        (1..=100u64)
+            .rev()
+            .map(|id| {
+                serde_json::from_value(serde_json::json!({
+                    "id": id.to_string(),
+                    "channel_id": "1",
+                    "author": {"id": "2", "username": "test", "discriminator": "0001"},
+                    "content": "",
+                    "timestamp": format!("2021-01-01T{:02}:{:02}:00+00:00", id / 60, id % 60),
+                    "edited_timestamp": null,
+                    "tts": false,
+                    "mention_everyone": false,
+                    "mentions": [],
+                    "mention_roles": [],
+                    "attachments": [],
+                    "embeds": [],
+                    "pinned": false,
+                    "type": 0,
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn ids(messages: &[Message]) -> Vec<u64> {
+        messages.iter().map(|message| (message.id.0).0).collect()
+    }
+
+    fn id(id: u64) -> MessageId {
+        MessageId(Snowflake(id))
+    }
+
+    #[test]
+    fn pages_are_ordered_in_walking_direction() {
+        let (messages, next) = process_page(page(), GetMessages::MostRecent, None);
+        assert_eq!(ids(&messages), (1..=100).rev().collect::<Vec<_>>());
+        assert!(matches!(next, Some(GetMessages::Before(before)) if before == id(1)));
+
+        let mut shuffled = page();
+        shuffled.swap(0, 50);
+        let (messages, next) = process_page(shuffled, GetMessages::After(id(0)), None);
+        assert_eq!(ids(&messages), (1..=100).collect::<Vec<_>>());
+        assert!(matches!(next, Some(GetMessages::After(after)) if after == id(100)));
+
+        let (_, next) = process_page(page(), GetMessages::Around(id(50)), None);
+        assert!(next.is_none());
+    }
+
+    #[test]
+    fn short_pages_end_the_walk() {
+        let mut messages = page();
+        messages.truncate(10);
+        let (messages, next) = process_page(messages, GetMessages::MostRecent, None);
+        assert_eq!(messages.len(), 10);
+        assert!(next.is_none());
+    }
+
+    #[test]
+    fn stop_at_message_excludes_the_bound() {
+        let stop = Some(StopAt::Message(id(90)));
+        let (messages, next) = process_page(page(), GetMessages::MostRecent, stop);
+        assert_eq!(ids(&messages), (91..=100).rev().collect::<Vec<_>>());
+        assert!(next.is_none());
+
+        let stop = Some(StopAt::Message(id(10)));
+        let (messages, next) = process_page(page(), GetMessages::After(id(0)), stop);
+        assert_eq!(ids(&messages), (1..10).collect::<Vec<_>>());
+        assert!(next.is_none());
+    }
+
+    #[test]
+    fn stop_at_timestamp_excludes_messages_past_it() {
+        let time = DateTime::parse_from_rfc3339("2021-01-01T01:30:00+00:00").unwrap();
+        //message 90 was sent exactly at `time`, so walking backwards keeps it
+        let (messages, next) = process_page(
+            page(),
+            GetMessages::MostRecent,
+            Some(StopAt::Timestamp(time)),
+        );
+        assert_eq!(ids(&messages), (90..=100).rev().collect::<Vec<_>>());
+        assert!(next.is_none());
+
+        let (messages, next) = process_page(
+            page(),
+            GetMessages::After(id(0)),
+            Some(StopAt::Timestamp(time)),
+        );
+        assert_eq!(ids(&messages), (1..=90).collect::<Vec<_>>());
+        assert!(next.is_none());
+
+        //a bound beyond the page leaves it untouched
+        let time = DateTime::parse_from_rfc3339("2020-12-31T00:00:00+00:00").unwrap();
+        let (messages, next) = process_page(
+            page(),
+            GetMessages::MostRecent,
+            Some(StopAt::Timestamp(time)),
+        );
+        assert_eq!(messages.len(), 100);
+        assert!(next.is_some());
+    }
+}
@@ -0,0 +1,78 @@
+use super::Client;
+use crate::model::*;
+use crate::Error;
+
+#[derive(Serialize, Default)]
+pub struct NewInvite {
+    ///duration of invite in seconds before expiry, or 0 for never. between 0 and 604800 (7 days), defaults to 86400 (24 hours)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+    ///max number of uses or 0 for unlimited. between 0 and 100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u64>,
+    ///whether this invite only grants temporary membership
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temporary: Option<bool>,
+    ///if true, don't try to reuse a similar invite (useful for creating many unique one time use invites)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique: Option<bool>,
+    ///the type of target for this voice channel invite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_type: Option<InviteTargetType>,
+    ///the id of the user whose stream to display for this invite, required if `target_type` is `Stream`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_user_id: Option<UserId>,
+    ///the id of the embedded application to open for this invite, required if `target_type` is `EmbeddedApplication`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_application_id: Option<ApplicationId>,
+}
+
+impl Client {
+    pub async fn create_channel_invite(
+        &self,
+        channel_id: ChannelId,
+        invite: NewInvite,
+        reason: Option<&str>,
+    ) -> Result<Invite, Error> {
+        let url = format!(
+            "/channels/{channel_id}/invites",
+            channel_id = (channel_id.0).0
+        );
+        self.post_return_json(url, invite, reason).await
+    }
+
+    ///Invites for a channel, including their metadata. Requires MANAGE_CHANNELS
+    pub async fn get_channel_invites(&self, channel_id: ChannelId) -> Result<Vec<Invite>, Error> {
+        let url = format!(
+            "/channels/{channel_id}/invites",
+            channel_id = (channel_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    ///Invites for every channel in a guild, including their metadata. Requires MANAGE_GUILD
+    pub async fn get_guild_invites(&self, guild_id: GuildId) -> Result<Vec<Invite>, Error> {
+        let url = format!("/guilds/{guild_id}/invites", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn get_invite(
+        &self,
+        code: &str,
+        with_counts: bool,
+        with_expiration: bool,
+    ) -> Result<Invite, Error> {
+        let url = format!(
+            "/invites/{code}?with_counts={with_counts}&with_expiration={with_expiration}",
+            code = code,
+            with_counts = with_counts,
+            with_expiration = with_expiration
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn delete_invite(&self, code: &str, reason: Option<&str>) -> Result<Invite, Error> {
+        let url = format!("/invites/{code}", code = code);
+        self.delete_return_json(url, reason).await
+    }
+}
@@ -0,0 +1,318 @@
+use super::paginate::{next_cursor, paginate};
+use super::Client;
+use crate::model::*;
+use crate::Error;
+use futures::Stream;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+
+#[derive(Default, Serialize)]
+pub struct EditMember {
+    ///value to set the user's nickname to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nick: Option<String>,
+    ///the roles the member is assigned, replacing all existing roles
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<RoleId>>,
+    ///whether the user is muted in voice channels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    ///whether the user is deafened in voice channels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+    ///id of the voice channel to move the user to, if they are connected to voice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+}
+
+#[derive(Default, Serialize)]
+pub struct EditRole {
+    ///name of the role
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    ///bitwise value of the enabled/disabled permissions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    ///RGB color value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    ///whether the role should be displayed separately in the sidebar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hoist: Option<bool>,
+    ///whether the role should be mentionable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mentionable: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct RolePosition {
+    ///role id
+    pub id: RoleId,
+    ///sorting position of the role
+    pub position: u64,
+}
+
+#[derive(Serialize)]
+struct CreateBan {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delete_message_days: Option<u64>,
+}
+
+impl Client {
+    ///Lists up to `limit` (1-1000) members, ordered by user id. Pass the last id returned as `after` to get the next page
+    pub async fn list_guild_members(
+        &self,
+        guild_id: GuildId,
+        limit: Option<u64>,
+        after: Option<UserId>,
+    ) -> Result<Vec<GuildMember>, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/members?limit={limit}&after={after}",
+            guild_id = (guild_id.0).0,
+            limit = limit.unwrap_or(1),
+            after = after.map(|after| (after.0).0).unwrap_or(0)
+        );
+        self.get_json(url).await
+    }
+
+    ///Every member of a guild, ordered by user id, fetching further pages as needed. Requires the GUILD_MEMBERS intent
+    pub fn guild_members(
+        &self,
+        guild_id: GuildId,
+    ) -> impl Stream<Item = Result<GuildMember, Error>> + Send + 'static {
+        let client = self.clone();
+        paginate(None, move |after| {
+            let client = client.clone();
+            async move {
+                let members = client
+                    .list_guild_members(guild_id, Some(1000), after)
+                    .await?;
+                let next = next_cursor(&members, 1000, |member| Some(member.user.id));
+                Ok((members, next))
+            }
+        })
+    }
+
+    ///Members whose username or nickname starts with `query`
+    pub async fn search_guild_members(
+        &self,
+        guild_id: GuildId,
+        query: &str,
+        limit: Option<u64>,
+    ) -> Result<Vec<GuildMember>, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/members/search?query={query}&limit={limit}",
+            guild_id = (guild_id.0).0,
+            query = utf8_percent_encode(query, NON_ALPHANUMERIC),
+            limit = limit.unwrap_or(1)
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn get_guild_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<GuildMember, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/members/{user_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn modify_guild_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        edit: EditMember,
+        reason: Option<&str>,
+    ) -> Result<GuildMember, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/members/{user_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.patch_return_json(url, edit, reason).await
+    }
+
+    pub async fn add_guild_member_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/members/{user_id}/roles/{role_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0,
+            role_id = (role_id.0).0
+        );
+        self.put(url, reason).await
+    }
+
+    pub async fn remove_guild_member_role(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/members/{user_id}/roles/{role_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0,
+            role_id = (role_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+
+    ///Kicks a member from the guild
+    pub async fn remove_guild_member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/members/{user_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+
+    ///Lists up to `limit` (1-1000) bans, ordered by user id. Pass the last id returned as `after` to get the next page
+    pub async fn get_guild_bans(
+        &self,
+        guild_id: GuildId,
+        limit: Option<u64>,
+        after: Option<UserId>,
+    ) -> Result<Vec<Ban>, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/bans?limit={limit}&after={after}",
+            guild_id = (guild_id.0).0,
+            limit = limit.unwrap_or(1000),
+            after = after.map(|after| (after.0).0).unwrap_or(0)
+        );
+        self.get_json(url).await
+    }
+
+    ///Every ban in a guild, ordered by user id, fetching further pages as needed
+    pub fn guild_bans(
+        &self,
+        guild_id: GuildId,
+    ) -> impl Stream<Item = Result<Ban, Error>> + Send + 'static {
+        let client = self.clone();
+        paginate(None, move |after| {
+            let client = client.clone();
+            async move {
+                let bans = client.get_guild_bans(guild_id, Some(1000), after).await?;
+                let next = next_cursor(&bans, 1000, |ban| Some(ban.user.id));
+                Ok((bans, next))
+            }
+        })
+    }
+
+    pub async fn get_guild_ban(&self, guild_id: GuildId, user_id: UserId) -> Result<Ban, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/bans/{user_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    ///Bans a user, deleting their messages from the last `delete_message_days` days (0-7)
+    pub async fn create_guild_ban(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        delete_message_days: Option<u64>,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/bans/{user_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.put_json(
+            url,
+            CreateBan {
+                delete_message_days,
+            },
+            reason,
+        )
+        .await
+    }
+
+    pub async fn remove_guild_ban(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/bans/{user_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+
+    pub async fn get_guild_roles(&self, guild_id: GuildId) -> Result<Vec<Role>, Error> {
+        let url = format!("/guilds/{guild_id}/roles", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn create_guild_role(
+        &self,
+        guild_id: GuildId,
+        role: EditRole,
+        reason: Option<&str>,
+    ) -> Result<Role, Error> {
+        let url = format!("/guilds/{guild_id}/roles", guild_id = (guild_id.0).0);
+        self.post_return_json(url, role, reason).await
+    }
+
+    ///Moves roles within a guild, returning all of the guild's roles
+    pub async fn modify_guild_role_positions(
+        &self,
+        guild_id: GuildId,
+        positions: Vec<RolePosition>,
+        reason: Option<&str>,
+    ) -> Result<Vec<Role>, Error> {
+        let url = format!("/guilds/{guild_id}/roles", guild_id = (guild_id.0).0);
+        self.patch_return_json(url, positions, reason).await
+    }
+
+    pub async fn modify_guild_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        edit: EditRole,
+        reason: Option<&str>,
+    ) -> Result<Role, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/roles/{role_id}",
+            guild_id = (guild_id.0).0,
+            role_id = (role_id.0).0
+        );
+        self.patch_return_json(url, edit, reason).await
+    }
+
+    pub async fn delete_guild_role(
+        &self,
+        guild_id: GuildId,
+        role_id: RoleId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/roles/{role_id}",
+            guild_id = (guild_id.0).0,
+            role_id = (role_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+}
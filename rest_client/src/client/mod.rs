@@ -1,24 +1,116 @@
 use crate::model::{self, *};
-use crate::Error;
-use crate::API_BASE;
+use crate::{DiscordApiError, Error};
 use itertools::Itertools;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use rand::Rng;
 use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use url::Url;
 
 use tracing::*;
 
+mod attachment;
+use attachment::Multipart;
+pub use attachment::{image_data_uri, Attachment};
+mod audit_log;
+pub use audit_log::*;
+mod builder;
+pub use builder::*;
+mod channel;
+pub use channel::*;
+mod emoji;
+pub use emoji::*;
+mod guild;
+pub use guild::*;
+mod history;
+pub use history::*;
+mod invite;
+pub use invite::*;
+mod member;
+pub use member::*;
+mod paginate;
+mod purge;
+pub use purge::*;
 mod ratelimiter;
+mod reaction;
+mod scheduled_event;
+pub use scheduled_event::*;
+mod stage;
+pub use stage::*;
+mod sticker;
+pub use sticker::*;
+mod template;
+pub use template::*;
+mod thread;
+pub use thread::*;
+mod webhook;
 use ratelimiter::*;
+pub use ratelimiter::{RateLimitCallback, RateLimitHit};
+pub use webhook::*;
+
+///the delay before the first retry of a request that failed with a server error, doubled on each subsequent retry
+const SERVER_ERROR_BACKOFF: Duration = Duration::from_millis(500);
+
+///whether a request can safely be sent again after a server error.
+///POST requests may have been processed before the error, so retrying them could e.g. send a message twice
+fn retries_server_errors(method: &reqwest::Method) -> bool {
+    matches!(
+        *method,
+        reqwest::Method::GET
+            | reqwest::Method::HEAD
+            | reqwest::Method::PUT
+            | reqwest::Method::DELETE
+            | reqwest::Method::PATCH
+    )
+}
+
+///the delay before retrying a request after it failed with a server error `retries` times, with some jitter
+fn server_error_backoff(retries: u16) -> Duration {
+    SERVER_ERROR_BACKOFF * 2u32.pow(retries.min(8).into())
+        + Duration::from_millis(rand::thread_rng().gen_range(0, 250))
+}
 
 #[derive(Clone)]
 pub struct Client {
     http_client: reqwest::Client,
-    bot_token: String,
+    ///the full value for the Authorization header, including the token type
+    authorization: Option<String>,
+    ///base url including the api version
+    api_base: String,
+    user_agent: String,
+    timeout: Option<Duration>,
     rate_limiter: RateLimiter,
+    on_rate_limit: Option<RateLimitCallback>,
     max_retries: u16,
 }
 
+enum RequestBody {
+    Empty,
+    Json(Vec<u8>),
+    Multipart(Multipart),
+}
+
+impl RequestBody {
+    ///`data` as json, or as the `payload_json` of a multipart body if there are any files
+    fn with_files<T: Serialize>(data: &T, files: &[Attachment]) -> Result<Self, Error> {
+        Ok(if files.is_empty() {
+            RequestBody::Json(serde_json::to_vec(data)?)
+        } else {
+            RequestBody::Multipart(Multipart::new(data, files)?)
+        })
+    }
+}
+
+///The body discord sends along with a 429
+#[derive(Deserialize)]
+struct RateLimitedResponse {
+    ///seconds until the request can be retried
+    retry_after: f64,
+    #[serde(default)]
+    global: bool,
+}
+
 #[derive(Default, Serialize)]
 pub struct EditMessage {
     ///the message contents (up to 2000 characters)
@@ -27,6 +119,9 @@ pub struct EditMessage {
     ///embedded rich content
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub embed: Option<model::Embed>,
+    ///files to attach to the message
+    #[serde(skip)]
+    pub files: Vec<Attachment>,
     //TODO: flags
 }
 
@@ -59,6 +154,11 @@ impl EditMessage {
         self
     }
 
+    pub fn with_file(mut self, file: Attachment) -> Self {
+        self.files.push(file);
+        self
+    }
+
     pub fn enforce_embed_limits(&self) -> Result<(), EmbedTooBigError> {
         if let Some(embed) = self.embed.as_ref() {
             embed.enforce_embed_limits()?;
@@ -77,12 +177,12 @@ pub struct NewMessage {
     ///true if this is a TTS message
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tts: Option<bool>,
-    // ///the contents of the file being sent. Used in multipart requests (not currently supported)
-    // pub file: Vec<u8>
     ///embedded rich content
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub embed: Option<model::Embed>, // ///JSON encoded body of any additional request fields. Used for multipart requests (not currently supported)
-                                     // pub payload_json: String
+    pub embed: Option<model::Embed>,
+    ///files to attach to the message, sent as a multipart request
+    #[serde(skip)]
+    pub files: Vec<Attachment>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -94,7 +194,7 @@ pub enum GetMessages {
 }
 
 impl GetMessages {
-    fn to_query(&self, query: &mut HashMap<&'static str, String>) {
+    fn to_query(self, query: &mut HashMap<&'static str, String>) {
         match self {
             GetMessages::Around(msg_id) => {
                 query.insert("around", (msg_id.0).0.to_string());
@@ -136,6 +236,11 @@ impl NewMessage {
         self
     }
 
+    pub fn with_file(mut self, file: Attachment) -> Self {
+        self.files.push(file);
+        self
+    }
+
     pub fn enforce_embed_limits(&self) -> Result<(), EmbedTooBigError> {
         if let Some(embed) = self.embed.as_ref() {
             embed.enforce_embed_limits()?;
@@ -145,20 +250,22 @@ impl NewMessage {
 }
 
 impl Client {
+    ///Creates a client for a bot token with the default configuration
     pub fn new<S: Into<String>>(bot_token: S) -> Self {
-        Self {
-            http_client: reqwest::Client::new(),
-            bot_token: bot_token.into(),
-            max_retries: 5,
-            rate_limiter: Default::default(),
-        }
+        ClientBuilder::new(bot_token)
+            .build()
+            .expect("Couldn't build http client")
+    }
+
+    pub fn builder<S: Into<String>>(token: S) -> ClientBuilder {
+        ClientBuilder::new(token)
     }
 
     pub async fn update_message(
         &self,
         channel_id: ChannelId,
         message_id: MessageId,
-        edit_message: EditMessage,
+        mut edit_message: EditMessage,
     ) -> Result<Message, Error> {
         edit_message.enforce_embed_limits()?;
         let url = format!(
@@ -166,34 +273,38 @@ impl Client {
             channel_id = (channel_id.0).0,
             message_id = (message_id.0).0
         );
-        self.patch_return_json(None, url, edit_message).await
+        let files = std::mem::take(&mut edit_message.files);
+        self.send_with_files(reqwest::Method::PATCH, url, edit_message, files)
+            .await
     }
 
     pub async fn send_message(
         &self,
         channel_id: ChannelId,
-        new_message: NewMessage,
+        mut new_message: NewMessage,
     ) -> Result<Message, Error> {
         new_message.enforce_embed_limits()?;
         let url = format!(
             "/channels/{channel_id}/messages",
             channel_id = (channel_id.0).0
         );
-        self.post_return_json(None, url, new_message).await
+        let files = std::mem::take(&mut new_message.files);
+        self.send_with_files(reqwest::Method::POST, url, new_message, files)
+            .await
     }
 
     pub async fn get_guilds(&self) -> Result<Vec<PartialGuild>, Error> {
-        self.get_json(None, "/users/@me/guilds").await
+        self.get_json("/users/@me/guilds").await
     }
 
     pub async fn get_guild_channels(&self, guild_id: GuildId) -> Result<Vec<Channel>, Error> {
         let url = format!("/guilds/{guild_id}/channels", guild_id = (guild_id.0).0);
-        self.get_json(None, url).await
+        self.get_json(url).await
     }
 
     pub async fn create_private_channel(&self, recipient_id: UserId) -> Result<Channel, Error> {
         let url = "/users/@me/channels";
-        self.post_return_json(None, url, json!({ "recipient_id": recipient_id }))
+        self.post_return_json(url, json!({ "recipient_id": recipient_id }), None)
             .await
     }
 
@@ -214,195 +325,241 @@ impl Client {
         } else {
             String::new()
         };
-        let limit_url = format!(
-            "/channels/{channel_id}/messages",
-            channel_id = (channel_id.0).0
-        );
         let url = format!(
-            "{limit_url}{query_string}",
-            limit_url = limit_url,
+            "/channels/{channel_id}/messages{query_string}",
+            channel_id = (channel_id.0).0,
             query_string = query_string
         );
-        self.get_json(limit_url, url).await
+        self.get_json(url).await
     }
 
     pub async fn delete_message(
         &self,
         channel_id: ChannelId,
         message_id: MessageId,
+        reason: Option<&str>,
     ) -> Result<(), Error> {
-        //delete is not under the same rate limiter as other verbs, so we prepend delete to get a different limiter
-        let limit_url = format!(
-            "DELETE /channels/{channel_id}/messages/{{}}",
-            channel_id = (channel_id.0).0
-        );
         let url = format!(
             "/channels/{channel_id}/messages/{message_id}",
             channel_id = (channel_id.0).0,
             message_id = (message_id.0).0
         );
-        self.delete(limit_url, url).await
+        self.delete(url, reason).await
     }
 
     pub async fn delete_messages<'a>(
         &'a self,
         channel_id: ChannelId,
         message_ids: &'a [MessageId],
+        reason: Option<&'a str>,
     ) -> Result<(), Error> {
-        //delete is not under the same rate limiter as other verbs, so we prepend delete to get a different limiter
-        let limit_url = format!(
-            "DELETE /channels/{channel_id}/messages/bulk_delete",
-            channel_id = (channel_id.0).0
-        );
         let url = format!(
             "/channels/{channel_id}/messages/bulk_delete",
             channel_id = (channel_id.0).0
         );
-        self.post_json(limit_url, url, json!({ "messages": message_ids }))
+        self.post_json(url, json!({ "messages": message_ids }), reason)
             .await
     }
 
-    async fn delete<S1, S2>(&self, limit_url: S1, url: S2) -> Result<(), Error>
+    async fn delete<S>(&self, url: S, reason: Option<&str>) -> Result<(), Error>
     where
-        S1: Into<Option<String>> + 'static,
-        S2: AsRef<str> + 'static,
+        S: AsRef<str> + 'static,
+    {
+        self.execute_request(reqwest::Method::DELETE, url, RequestBody::Empty, reason)
+            .await?;
+        Ok(())
+    }
+
+    async fn put<S>(&self, url: S, reason: Option<&str>) -> Result<(), Error>
+    where
+        S: AsRef<str> + 'static,
+    {
+        self.execute_request(reqwest::Method::PUT, url, RequestBody::Empty, reason)
+            .await?;
+        Ok(())
+    }
+
+    async fn put_json<T, S>(&self, url: S, data: T, reason: Option<&str>) -> Result<(), Error>
+    where
+        T: Serialize + 'static,
+        S: AsRef<str> + 'static,
     {
         self.execute_request(
-            reqwest::Method::DELETE,
-            limit_url,
+            reqwest::Method::PUT,
             url,
-            reqwest::Body::from(&[] as &[u8]),
+            RequestBody::Json(serde_json::to_vec(&data)?),
+            reason,
         )
         .await?;
         Ok(())
     }
 
-    async fn get_json<T, S1, S2>(&self, limit_url: S1, url: S2) -> Result<T, Error>
+    async fn get_json<T, S>(&self, url: S) -> Result<T, Error>
     where
         T: DeserializeOwned + Unpin + 'static,
-        S1: Into<Option<String>> + 'static,
-        S2: AsRef<str> + 'static,
+        S: AsRef<str> + 'static,
     {
         let res = self
-            .execute_request(
-                reqwest::Method::GET,
-                limit_url,
-                url,
-                reqwest::Body::from(&[] as &[u8]),
-            )
+            .execute_request(reqwest::Method::GET, url, RequestBody::Empty, None)
             .await?;
         Ok(res.json().await?)
     }
 
-    async fn post_json<T, S1, S2>(&self, limit_url: S1, url: S2, data: T) -> Result<(), Error>
+    async fn post_json<T, S>(&self, url: S, data: T, reason: Option<&str>) -> Result<(), Error>
     where
         T: Serialize + 'static,
-        S1: Into<Option<String>> + 'static,
-        S2: AsRef<str> + 'static,
+        S: AsRef<str> + 'static,
     {
         self.execute_request(
             reqwest::Method::POST,
-            limit_url,
             url,
-            reqwest::Body::from(serde_json::to_string(&data)?),
+            RequestBody::Json(serde_json::to_vec(&data)?),
+            reason,
         )
         .await?;
         Ok(())
     }
 
-    async fn patch_json<T, S1, S2>(&self, limit_url: S1, url: S2, data: T) -> Result<(), Error>
+    async fn patch_json<T, S>(&self, url: S, data: T, reason: Option<&str>) -> Result<(), Error>
     where
         T: Serialize + 'static,
-        S1: Into<Option<String>> + 'static,
-        S2: AsRef<str> + 'static,
+        S: AsRef<str> + 'static,
     {
         self.execute_request(
             reqwest::Method::PATCH,
-            limit_url,
             url,
-            reqwest::Body::from(serde_json::to_string(&data)?),
+            RequestBody::Json(serde_json::to_vec(&data)?),
+            reason,
         )
         .await?;
         Ok(())
     }
 
-    async fn post_return_json<R, T, S1, S2>(
+    async fn post_return_json<R, T, S>(
         &self,
-        limit_url: S1,
-        url: S2,
+        url: S,
         data: T,
+        reason: Option<&str>,
     ) -> Result<R, Error>
     where
         T: Serialize + 'static,
         R: DeserializeOwned + Unpin + 'static,
-        S1: Into<Option<String>> + 'static,
-        S2: AsRef<str> + 'static,
+        S: AsRef<str> + 'static,
     {
         let res = self
             .execute_request(
                 reqwest::Method::POST,
-                limit_url,
                 url,
-                reqwest::Body::from(serde_json::to_string(&data)?),
+                RequestBody::Json(serde_json::to_vec(&data)?),
+                reason,
             )
             .await?;
         Ok(res.json().await?)
     }
 
-    async fn patch_return_json<R, T, S1, S2>(
+    async fn patch_return_json<R, T, S>(
         &self,
-        limit_url: S1,
-        url: S2,
+        url: S,
         data: T,
+        reason: Option<&str>,
     ) -> Result<R, Error>
     where
         T: Serialize + 'static,
         R: DeserializeOwned + Unpin + 'static,
-        S1: Into<Option<String>> + 'static,
-        S2: AsRef<str> + 'static,
+        S: AsRef<str> + 'static,
     {
         let res = self
             .execute_request(
                 reqwest::Method::PATCH,
-                limit_url,
                 url,
-                reqwest::Body::from(serde_json::to_string(&data)?),
+                RequestBody::Json(serde_json::to_vec(&data)?),
+                reason,
             )
             .await?;
         Ok(res.json().await?)
     }
 
-    async fn execute_request<S1, S2>(
+    async fn delete_return_json<R, S>(&self, url: S, reason: Option<&str>) -> Result<R, Error>
+    where
+        R: DeserializeOwned + Unpin + 'static,
+        S: AsRef<str> + 'static,
+    {
+        let res = self
+            .execute_request(reqwest::Method::DELETE, url, RequestBody::Empty, reason)
+            .await?;
+        Ok(res.json().await?)
+    }
+
+    async fn put_return_json<R, S>(&self, url: S, reason: Option<&str>) -> Result<R, Error>
+    where
+        R: DeserializeOwned + Unpin + 'static,
+        S: AsRef<str> + 'static,
+    {
+        let res = self
+            .execute_request(reqwest::Method::PUT, url, RequestBody::Empty, reason)
+            .await?;
+        Ok(res.json().await?)
+    }
+
+    ///Sends `data` as json, or as the `payload_json` of a multipart request if there are any files
+    async fn send_with_files<R, T, S>(
         &self,
         method: reqwest::Method,
-        limit_url: S1,
-        url: S2,
-        body: reqwest::Body,
+        url: S,
+        data: T,
+        files: Vec<Attachment>,
+    ) -> Result<R, Error>
+    where
+        T: Serialize + 'static,
+        R: DeserializeOwned + Unpin + 'static,
+        S: AsRef<str> + 'static,
+    {
+        let body = RequestBody::with_files(&data, &files)?;
+        let res = self.execute_request(method, url, body, None).await?;
+        Ok(res.json().await?)
+    }
+
+    async fn execute_request<S>(
+        &self,
+        method: reqwest::Method,
+        url: S,
+        body: RequestBody,
+        reason: Option<&str>,
     ) -> Result<reqwest::Response, Error>
     where
-        S1: Into<Option<String>> + 'static,
-        S2: AsRef<str> + 'static,
+        S: AsRef<str> + 'static,
     {
         let mut retries = 0;
         let url = url.as_ref();
-        let limit_url = limit_url.into().unwrap_or_else(|| url.to_owned());
+        let route = Route::new(&method, url);
 
-        let absolute_url = format!("{base_url}{url}", base_url = API_BASE, url = url);
+        let absolute_url = format!("{base_url}{url}", base_url = self.api_base, url = url);
 
         let req_builder = self.http_client.request(method.clone(), &absolute_url);
 
         let mut req_builder = self.set_headers(req_builder);
-        if body.as_bytes().map(|b| b.len()).unwrap_or(0) > 0 {
-            req_builder = req_builder.header("Content-Type", "application/json");
+        if let Some(reason) = reason {
+            req_builder = req_builder.header(
+                "X-Audit-Log-Reason",
+                utf8_percent_encode(reason, NON_ALPHANUMERIC).to_string(),
+            );
         }
-        let req_builder = req_builder.body(body);
+        let req_builder = match body {
+            RequestBody::Empty => req_builder.body(Vec::new()),
+            RequestBody::Json(json) => req_builder
+                .header("Content-Type", "application/json")
+                .body(json),
+            RequestBody::Multipart(multipart) => req_builder
+                .header("Content-Type", multipart.content_type())
+                .body(multipart.body),
+        };
 
         let req = req_builder.build()?;
 
         trace!("request: {:?}", req);
 
         'retry_loop: loop {
-            self.rate_limiter.enforce_limit(&limit_url).await?;
+            self.rate_limiter.enforce_limit(&route).await?;
 
             let send_req = req
                 .try_clone()
@@ -410,37 +567,100 @@ impl Client {
 
             let res = self.http_client.execute(send_req).await?;
 
-            self.rate_limiter
-                .update_limits(limit_url.clone(), res.headers());
+            self.rate_limiter.update_limits(&route, res.headers());
 
             if res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
                 if retries >= self.max_retries {
                     return Err(Error::TooManyRetries(self.max_retries, url.to_owned()));
                 }
                 retries += 1;
+                self.handle_rate_limited(&route, res).await;
+                continue 'retry_loop;
+            }
+
+            if res.status().is_server_error()
+                && retries < self.max_retries
+                && retries_server_errors(&method)
+            {
+                let backoff = server_error_backoff(retries);
+                warn!(
+                    "Request to {} failed with {}, retrying in {:?}",
+                    route,
+                    res.status(),
+                    backoff
+                );
+                retries += 1;
+                tokio::time::delay_for(backoff).await;
                 continue 'retry_loop;
             }
 
             if !res.status().is_success() {
                 let status = res.status();
-                error!("Request failed with result: {:?}", res.text().await?);
-                return Err(Error::UnsuccessfulHttp(status));
+                let body = res.text().await?;
+                return Err(match DiscordApiError::parse(status, &body) {
+                    Some(api_error) => {
+                        debug!("Request to {} failed: {:?}", route, api_error);
+                        api_error.into()
+                    }
+                    None => {
+                        error!("Request failed with result: {:?}", body);
+                        Error::UnsuccessfulHttp(status)
+                    }
+                });
             }
 
             return Ok(res);
         }
     }
 
+    ///Works out how long a 429 asks us to wait and blocks the rate limiter for that long
+    async fn handle_rate_limited(&self, route: &Route, res: reqwest::Response) {
+        let bucket = res
+            .headers()
+            .get("X-RateLimit-Bucket")
+            .and_then(|bucket| bucket.to_str().ok())
+            .map(String::from);
+        let global_header = res.headers().contains_key("X-RateLimit-Global");
+        let retry_after_header = res
+            .headers()
+            .get("Retry-After")
+            .and_then(|retry_after| retry_after.to_str().ok())
+            .and_then(|retry_after| retry_after.parse::<f64>().ok());
+        //the body has millisecond precision, where the header may be rounded to whole seconds
+        let (retry_after, global) = match res.json::<RateLimitedResponse>().await {
+            Ok(body) => (Some(body.retry_after), body.global || global_header),
+            Err(e) => {
+                debug!("Couldn't parse 429 body: {:?}", e);
+                (retry_after_header, global_header)
+            }
+        };
+        let retry_after = retry_after
+            .filter(|retry_after| retry_after.is_finite() && *retry_after >= 0.0)
+            .map(Duration::from_secs_f64)
+            .unwrap_or(SERVER_ERROR_BACKOFF);
+
+        let hit = RateLimitHit {
+            route: route.to_string(),
+            bucket,
+            retry_after,
+            global,
+        };
+        warn!("Rate limited: {:?}", hit);
+        self.rate_limiter.rate_limited(route, retry_after, global);
+        if let Some(on_rate_limit) = self.on_rate_limit.as_ref() {
+            on_rate_limit(&hit);
+        }
+    }
+
     fn set_headers(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        builder
-            .header(
-                "Authorization",
-                format!("Bot {bot_token}", bot_token = self.bot_token),
-            )
-            .header(
-                "User-Agent",
-                "discord-next-rs (github.com/Eroc33, 0.0.1-prototype)",
-            )
+        let mut builder = builder.header("User-Agent", &self.user_agent);
+        if let Some(authorization) = self.authorization.as_ref() {
+            builder = builder.header("Authorization", authorization);
+        }
+        match self.timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
     }
 
     pub async fn get_gateway(&self, version: u8) -> Result<Url, Error> {
@@ -448,12 +668,16 @@ impl Client {
         struct GatewayResponse {
             url: String,
         }
-        let res: GatewayResponse = self.get_json(None, "/gateway").await?;
+        let res: GatewayResponse = self.get_json("/gateway").await?;
         Ok(Url::parse(
             format!("{}?v={}&encoding=json", res.url, version).as_str(),
         )?)
     }
 
+    pub async fn get_gateway_bot(&self) -> Result<GatewayBot, Error> {
+        self.get_json("/gateway/bot").await
+    }
+
     pub async fn get_application_commands(
         &self,
         application_id: ApplicationId,
@@ -462,7 +686,7 @@ impl Client {
             "/applications/{application_id}/commands",
             application_id = (application_id.0).0
         );
-        self.get_json(None, url).await
+        self.get_json(url).await
     }
 
     pub async fn create_application_command(
@@ -474,7 +698,7 @@ impl Client {
             "/applications/{application_id}/commands",
             application_id = (application_id.0).0
         );
-        self.post_return_json(None, url, command).await
+        self.post_return_json(url, command, None).await
     }
 
     pub async fn delete_application_command(
@@ -487,7 +711,7 @@ impl Client {
             application_id = (application_id.0).0,
             command_id = (command_id.0).0,
         );
-        self.delete(None, url).await
+        self.delete(url, None).await
     }
 
     pub async fn create_interaction_response(
@@ -500,7 +724,7 @@ impl Client {
             interaction_id = (interaction.id.0).0,
             interaction_token = interaction.token,
         );
-        self.post_json(None, url, response).await
+        self.post_json(url, response, None).await
     }
 
     pub async fn edit_original_interaction_response(
@@ -513,6 +737,119 @@ impl Client {
             application_id = (interaction.application_id.0).0,
             interaction_token = interaction.token,
         );
-        self.patch_json(None, url, response).await
+        self.patch_json(url, response, None).await
+    }
+
+    pub async fn create_followup_message(
+        &self,
+        interaction: &Interaction,
+        message: InteractionApplicationCommandCallbackData,
+        files: Vec<Attachment>,
+    ) -> Result<Message, Error> {
+        let url = format!(
+            "/webhooks/{application_id}/{interaction_token}",
+            application_id = (interaction.application_id.0).0,
+            interaction_token = interaction.token,
+        );
+        self.send_with_files(reqwest::Method::POST, url, message, files)
+            .await
+    }
+
+    pub async fn edit_followup_message(
+        &self,
+        interaction: &Interaction,
+        message_id: MessageId,
+        message: InteractionApplicationCommandCallbackData,
+        files: Vec<Attachment>,
+    ) -> Result<Message, Error> {
+        let url = format!(
+            "/webhooks/{application_id}/{interaction_token}/messages/{message_id}",
+            application_id = (interaction.application_id.0).0,
+            interaction_token = interaction.token,
+            message_id = (message_id.0).0,
+        );
+        self.send_with_files(reqwest::Method::PATCH, url, message, files)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    ///serves the given status codes in order, one per request. Returns the base url and the statuses not yet served
+    async fn serve(statuses: Vec<u16>) -> (String, Arc<Mutex<VecDeque<u16>>>) {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let statuses = Arc::new(Mutex::new(VecDeque::from(statuses)));
+        let remaining = statuses.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let read = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+                let status = statuses.lock().unwrap().pop_front().unwrap();
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (base_url, remaining)
+    }
+
+    fn client(base_url: String) -> Client {
+        ClientBuilder::new("token")
+            .base_url(base_url)
+            .max_retries(2)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn only_idempotent_methods_retry_server_errors() {
+        assert!(retries_server_errors(&reqwest::Method::GET));
+        assert!(retries_server_errors(&reqwest::Method::PUT));
+        assert!(retries_server_errors(&reqwest::Method::PATCH));
+        assert!(retries_server_errors(&reqwest::Method::DELETE));
+        assert!(!retries_server_errors(&reqwest::Method::POST));
+    }
+
+    #[test]
+    fn server_error_backoff_doubles_up_to_a_limit() {
+        for retries in 0..12 {
+            let backoff = server_error_backoff(retries);
+            let base = SERVER_ERROR_BACKOFF * 2u32.pow(retries.min(8).into());
+            assert!(backoff >= base && backoff < base + Duration::from_millis(250));
+        }
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried() {
+        let (base_url, remaining) = serve(vec![502, 204]).await;
+        client(base_url)
+            .delete_message(ChannelId(Snowflake(1)), MessageId(Snowflake(2)), None)
+            .await
+            .unwrap();
+        assert!(remaining.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn post_is_not_retried() {
+        let (base_url, remaining) = serve(vec![502, 204]).await;
+        let result = client(base_url)
+            .trigger_typing_indicator(ChannelId(Snowflake(1)))
+            .await;
+        assert!(result.is_err());
+        assert_eq!(remaining.lock().unwrap().len(), 1);
     }
 }
@@ -0,0 +1,79 @@
+use crate::Error;
+use futures::{stream, Future, Stream, TryStreamExt};
+
+///Turns a paged endpoint into a stream of its items
+///
+///`fetch` is called with the cursor for each page in turn, and returns that page along with the cursor for the next one,
+///or `None` once there's nothing left to fetch. Pages are only requested as the stream is polled,
+///and each request goes through the rate limiter like any other.
+pub(crate) fn paginate<C, T, F, Fut>(
+    start: C,
+    fetch: F,
+) -> impl Stream<Item = Result<T, Error>> + Send + 'static
+where
+    C: Send + 'static,
+    T: Send + 'static,
+    F: FnMut(C) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(Vec<T>, Option<C>), Error>> + Send + 'static,
+{
+    let pages = stream::unfold((Some(start), fetch), |(cursor, mut fetch)| async move {
+        let cursor = cursor?;
+        match fetch(cursor).await {
+            Ok((page, next)) => Some((Ok(page), (next, fetch))),
+            Err(e) => Some((Err(e), (None, fetch))),
+        }
+    });
+    pages
+        .map_ok(|page| stream::iter(page.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+///The cursor for the page after `page`, if it was full
+pub(crate) fn next_cursor<T, C, F>(page: &[T], limit: u64, cursor: F) -> Option<C>
+where
+    F: FnOnce(&T) -> C,
+{
+    if (page.len() as u64) < limit {
+        //a short page means there's nothing further
+        None
+    } else {
+        page.last().map(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{executor::block_on, StreamExt};
+
+    #[test]
+    fn pages_are_fetched_until_a_short_page() {
+        let items = block_on(
+            paginate(0u64, |after| async move {
+                let page: Vec<u64> = (after + 1..=after + 3).filter(|i| *i <= 7).collect();
+                let next = next_cursor(&page, 3, |last| *last);
+                Ok((page, next))
+            })
+            .collect::<Vec<_>>(),
+        );
+        let items: Vec<u64> = items.into_iter().map(Result::unwrap).collect();
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn errors_end_the_stream() {
+        let items = block_on(
+            paginate(0u64, |page| async move {
+                if page == 0 {
+                    Ok((vec![page], Some(1)))
+                } else {
+                    Err(Error::UnsuccessfulHttp(reqwest::StatusCode::BAD_GATEWAY))
+                }
+            })
+            .collect::<Vec<_>>(),
+        );
+        assert_eq!(items.len(), 2);
+        assert!(items[0].is_ok());
+        assert!(items[1].is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use super::{Client, GetMessages, StopAt};
+use crate::model::*;
+use crate::Error;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use futures::TryStreamExt;
+use regex::Regex;
+
+///bulk_delete accepts at most this many messages at once
+const BULK_DELETE_MAX: usize = 100;
+
+type MessagePredicate = Box<dyn Fn(&Message) -> bool + Send + Sync>;
+
+///Which messages `Client::purge` deletes, all set conditions must match
+#[derive(Default)]
+pub struct PurgeFilter {
+    authors: Vec<UserId>,
+    content: Option<Regex>,
+    has_attachments: Option<bool>,
+    newer_than: Option<DateTime<FixedOffset>>,
+    limit: Option<usize>,
+    predicate: Option<MessagePredicate>,
+}
+
+impl PurgeFilter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///only messages sent by this user, can be given more than once to match any of several users
+    pub fn from_user(mut self, user_id: UserId) -> Self {
+        self.authors.push(user_id);
+        self
+    }
+
+    ///only messages whose content matches `regex`
+    pub fn content_matches(mut self, regex: Regex) -> Self {
+        self.content = Some(regex);
+        self
+    }
+
+    ///only messages with (or without) attachments
+    pub fn has_attachments(mut self, has_attachments: bool) -> Self {
+        self.has_attachments = Some(has_attachments);
+        self
+    }
+
+    ///only messages sent after `time`, history older than this isn't fetched
+    pub fn newer_than(mut self, time: DateTime<FixedOffset>) -> Self {
+        self.newer_than = Some(time);
+        self
+    }
+
+    ///delete at most this many messages, the most recent first
+    ///
+    ///Without a limit or `newer_than` the channel's entire history is scanned
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    ///only messages for which `f` returns true
+    pub fn matching<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Message) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Some(Box::new(f));
+        self
+    }
+
+    fn matches(&self, message: &Message) -> bool {
+        (self.authors.is_empty() || self.authors.contains(&message.author.id))
+            && self
+                .content
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&message.content))
+            && self
+                .has_attachments
+                .is_none_or(|has| has != message.attachments.is_empty())
+            && self
+                .predicate
+                .as_ref()
+                .is_none_or(|predicate| predicate(message))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PurgeReport {
+    ///the messages that were deleted
+    pub deleted: Vec<MessageId>,
+    ///the messages that couldn't be deleted, grouped by the request that failed
+    pub failed: Vec<PurgeFailure>,
+}
+
+#[derive(Debug)]
+pub struct PurgeFailure {
+    pub messages: Vec<MessageId>,
+    pub error: Error,
+}
+
+impl Client {
+    ///Deletes the messages in a channel that match `filter`, most recent first
+    ///
+    ///Messages are deleted in bulk where discord allows it (2-100 messages less than 14 days old),
+    ///anything else is deleted one at a time. Deletion happens in chunks while the history is still being fetched,
+    ///so purging a long history doesn't hold all of it in memory.
+    ///Failed deletions don't stop the purge, they're listed in the report.
+    ///An error is only returned if fetching the channel's history fails, messages deleted up to that point stay deleted.
+    pub async fn purge(
+        &self,
+        channel_id: ChannelId,
+        filter: PurgeFilter,
+        reason: Option<&str>,
+    ) -> Result<PurgeReport, Error> {
+        let mut history = Box::pin(self.history(
+            channel_id,
+            GetMessages::MostRecent,
+            filter.newer_than.map(StopAt::Timestamp),
+        ));
+        let mut report = PurgeReport::default();
+        let mut targets = Vec::with_capacity(BULK_DELETE_MAX);
+        let mut found = 0;
+        while filter.limit.is_none_or(|limit| found < limit) {
+            match history.try_next().await? {
+                Some(message) if filter.matches(&message) => {
+                    found += 1;
+                    targets.push((message.id, message.timestamp));
+                    if targets.len() == BULK_DELETE_MAX {
+                        self.delete_targets(channel_id, &targets, reason, &mut report)
+                            .await;
+                        targets.clear();
+                    }
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+        self.delete_targets(channel_id, &targets, reason, &mut report)
+            .await;
+        Ok(report)
+    }
+
+    async fn delete_targets(
+        &self,
+        channel_id: ChannelId,
+        targets: &[(MessageId, DateTime<FixedOffset>)],
+        reason: Option<&str>,
+        report: &mut PurgeReport,
+    ) {
+        let (bulk, single) = plan_deletion(targets, Utc::now());
+        for chunk in bulk {
+            match self.delete_messages(channel_id, &chunk, reason).await {
+                Ok(()) => report.deleted.extend(chunk),
+                Err(error) => report.failed.push(PurgeFailure {
+                    messages: chunk,
+                    error,
+                }),
+            }
+        }
+        for message_id in single {
+            match self.delete_message(channel_id, message_id, reason).await {
+                Ok(()) => report.deleted.push(message_id),
+                Err(error) => report.failed.push(PurgeFailure {
+                    messages: vec![message_id],
+                    error,
+                }),
+            }
+        }
+    }
+}
+
+///Splits messages into valid bulk_delete chunks, and the rest that have to be deleted one at a time
+fn plan_deletion(
+    messages: &[(MessageId, DateTime<FixedOffset>)],
+    now: DateTime<Utc>,
+) -> (Vec<Vec<MessageId>>, Vec<MessageId>) {
+    //leave some slack so messages don't age out between planning and the request
+    let cutoff = now - Duration::days(14) + Duration::minutes(5);
+    let (recent, old): (Vec<_>, Vec<_>) = messages
+        .iter()
+        .partition(|(_, timestamp)| *timestamp > cutoff);
+
+    let mut bulk = Vec::new();
+    let mut single = Vec::new();
+    for chunk in recent.chunks(BULK_DELETE_MAX) {
+        let ids: Vec<MessageId> = chunk.iter().map(|(id, _)| *id).collect();
+        if ids.len() >= 2 {
+            bulk.push(ids);
+        } else {
+            single.extend(ids);
+        }
+    }
+    single.extend(old.into_iter().map(|message| message.0));
+    (bulk, single)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: u64, age: Duration) -> (MessageId, DateTime<FixedOffset>) {
+        (MessageId(Snowflake(id)), (Utc::now() - age).into())
+    }
+
+    #[test]
+    fn deletions_are_split_into_valid_bulk_chunks() {
+        let mut messages: Vec<_> = (0..201).map(|id| message(id, Duration::hours(1))).collect();
+        messages.push(message(500, Duration::days(15)));
+
+        let (bulk, single) = plan_deletion(&messages, Utc::now());
+        assert_eq!(
+            bulk.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![100, 100]
+        );
+        assert_eq!(
+            single,
+            vec![MessageId(Snowflake(200)), MessageId(Snowflake(500))]
+        );
+    }
+}
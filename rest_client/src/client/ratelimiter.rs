@@ -35,7 +35,18 @@ impl RateLimitState {
     }
 
     pub fn time_to_reset(&self) -> Duration {
-        Duration::from_secs((self.reset_at.timestamp() - Utc::now().timestamp()) as u64)
+        (self.reset_at - Utc::now()).to_std().unwrap_or_default()
+    }
+
+    ///a state with nothing remaining until `retry_after` has passed
+    fn exhausted(limit: usize, retry_after: Duration) -> Self {
+        RateLimitState {
+            limit,
+            remaining: 0,
+            reset_at: Utc::now()
+                + chrono::Duration::from_std(retry_after)
+                    .unwrap_or_else(|_| chrono::Duration::zero()),
+        }
     }
 
     pub fn parse(headers: &http::HeaderMap) -> Result<Option<Self>, anyhow::Error> {
@@ -66,19 +77,119 @@ impl RateLimitState {
     }
 }
 
+///Details of a 429 response, passed to the callback set with `ClientBuilder::on_rate_limit`
+#[derive(Debug, Clone)]
+pub struct RateLimitHit {
+    ///the route template of the request that was limited
+    pub route: String,
+    ///the bucket discord reported for the route, if any
+    pub bucket: Option<String>,
+    ///how long until the request can be retried
+    pub retry_after: Duration,
+    ///whether this was the global rate limit rather than the route's
+    pub global: bool,
+}
+
+pub type RateLimitCallback = Arc<dyn Fn(&RateLimitHit) + Send + Sync>;
+
+///Identifies the rate limit a request falls under: the method and path with every
+///id replaced by placeholders, and the major parameters (channel, guild and webhook) kept separately
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Route {
+    ///the route template, e.g. `DELETE /channels/{channel_id}/messages/{id}`
+    template: String,
+    ///the major parameters of the route, which split a shared bucket into separate limits
+    major_params: String,
+}
+
+impl Route {
+    pub fn new(method: &http::Method, path: &str) -> Self {
+        let path = path.split('?').next().unwrap_or("");
+        let mut template = String::new();
+        let mut major_params = Vec::new();
+        let mut previous: Option<&str> = None;
+        let mut before_previous: Option<&str> = None;
+        for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+            let templated = match (before_previous, previous) {
+                (_, Some("channels")) if is_id(segment) => {
+                    major_params.push(segment);
+                    "{channel_id}"
+                }
+                (_, Some("guilds")) if is_id(segment) => {
+                    major_params.push(segment);
+                    "{guild_id}"
+                }
+                (_, Some("webhooks")) if is_id(segment) => {
+                    major_params.push(segment);
+                    "{webhook_id}"
+                }
+                //webhook tokens are a major parameter alongside the webhook id
+                (Some("webhooks"), Some(id)) if is_id(id) => {
+                    major_params.push(segment);
+                    "{webhook_token}"
+                }
+                //all reactions on a message share a limit, regardless of emoji
+                (_, Some("reactions")) => "{emoji}",
+                //invite and template codes aren't major parameters
+                (_, Some("invites")) | (_, Some("templates")) => "{code}",
+                (Some("interactions"), Some(id)) if is_id(id) => "{token}",
+                _ if is_id(segment) => "{id}",
+                _ => segment,
+            };
+            template.push('/');
+            template.push_str(templated);
+            before_previous = previous;
+            previous = Some(segment);
+        }
+        Route {
+            template: format!("{} {}", method, template),
+            major_params: major_params.join("/"),
+        }
+    }
+}
+
+impl std::fmt::Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+fn is_id(segment: &str) -> bool {
+    !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[derive(Debug, Default)]
+struct RateLimits {
+    ///maps route templates to the bucket discord told us they belong to.
+    ///Templates don't include major parameters, so this stays small however many channels and guilds are used
+    buckets_by_route: HashMap<String, String>,
+    ///keyed by bucket and major parameters, or by route template if the bucket isn't known yet
+    limits: HashMap<String, RateLimitState>,
+    global: Option<RateLimitState>,
+}
+
+impl RateLimits {
+    fn key(&self, route: &Route) -> String {
+        match self.buckets_by_route.get(&route.template) {
+            Some(bucket) => format!("{}:{}", bucket, route.major_params),
+            None => format!("{}:{}", route.template, route.major_params),
+        }
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct RateLimiter {
-    rate_limits: Arc<RwLock<HashMap<String, RateLimitState>>>,
+    rate_limits: Arc<RwLock<RateLimits>>,
 }
 
 impl RateLimiter {
     //TODO: this might have performance issues. investigate fine-grained locking
-    pub async fn enforce_limit<'a>(&'a self, endpoint: &'a str) -> Result<(), tokio::time::Error> {
+    pub async fn enforce_limit<'a>(&'a self, route: &'a Route) -> Result<(), tokio::time::Error> {
         loop {
             //get any required wait first, then run it once the rate_limits guard is dropped to avoid lock contention
             let wait = {
                 let guard = self.rate_limits.read().expect("Rate limits poisoned");
-                let wait_endpoint = if let Some(rate_limit) = guard.get(endpoint) {
+                let wait_route = if let Some(rate_limit) = guard.limits.get(&guard.key(route)) {
                     if rate_limit.get_remaining() == Some(0) {
                         Some(Instant::now() + rate_limit.time_to_reset())
                     } else {
@@ -87,7 +198,7 @@ impl RateLimiter {
                 } else {
                     None
                 };
-                let wait_global = if let Some(rate_limit) = guard.get("GLOBAL") {
+                let wait_global = if let Some(rate_limit) = guard.global.as_ref() {
                     if rate_limit.get_remaining() == Some(0) {
                         Some(Instant::now() + rate_limit.time_to_reset())
                     } else {
@@ -96,15 +207,15 @@ impl RateLimiter {
                 } else {
                     None
                 };
-                debug!("Rate limit state: {:?}", self.rate_limits.read());
-                std::cmp::max(wait_endpoint, wait_global)
+                debug!("Rate limit state: {:?}", *guard);
+                std::cmp::max(wait_route, wait_global)
             };
             if let Some(wait) = wait {
                 trace!("Waiting on ratelimit: {:?}", wait);
                 tokio::time::delay_until(tokio::time::Instant::from_std(wait)).await;
                 trace!("Rate limit wait complete");
             }
-            if self.use_resource(endpoint) {
+            if self.use_resource(route) {
                 return Ok(());
             }
             //something else used the new allocation of resource before we could.
@@ -112,23 +223,27 @@ impl RateLimiter {
         }
     }
 
-    fn use_resource(&self, endpoint: &str) -> bool {
+    fn use_resource(&self, route: &Route) -> bool {
         let mut guard = self.rate_limits.write().expect("Rate limits poisoned");
-        let endpoint_allowed = if let Some(rate_limit) = guard.get_mut(endpoint) {
+        let key = guard.key(route);
+        let route_allowed = if let Some(rate_limit) = guard.limits.get_mut(&key) {
             rate_limit.use_one()
         } else {
             true
         };
-        let global_allowed = if let Some(rate_limit) = guard.get_mut("GLOBAL") {
+        let global_allowed = if let Some(rate_limit) = guard.global.as_mut() {
             rate_limit.use_one()
         } else {
             true
         };
-        endpoint_allowed && global_allowed
+        route_allowed && global_allowed
     }
 
-    pub fn update_limits(&self, endpoint: String, headers: &http::HeaderMap) {
+    pub fn update_limits(&self, route: &Route, headers: &http::HeaderMap) {
         let global = headers.contains_key("X-RateLimit-Global");
+        let bucket = headers
+            .get("X-RateLimit-Bucket")
+            .and_then(|bucket| bucket.to_str().ok());
 
         let rate_limit = match RateLimitState::parse(headers) {
             Ok(Some(rate_limit)) => rate_limit,
@@ -143,8 +258,94 @@ impl RateLimiter {
             }
         };
 
-        let entry = if global { "GLOBAL".into() } else { endpoint };
+        let mut guard = self.rate_limits.write().expect("Rate limits poisoned");
+        if global {
+            guard.global = Some(rate_limit);
+            return;
+        }
+        if let Some(bucket) = bucket {
+            let known = guard
+                .buckets_by_route
+                .get(&route.template)
+                .map(|known| &known[..]);
+            if known != Some(bucket) {
+                trace!("Route {} is in bucket {}", route, bucket);
+                //anything tracked before we knew the bucket is superseded by the state below
+                let old_key = guard.key(route);
+                guard.limits.remove(&old_key);
+                guard
+                    .buckets_by_route
+                    .insert(route.template.clone(), bucket.to_owned());
+            }
+        }
+        let key = guard.key(route);
+        guard.limits.insert(key, rate_limit);
+    }
+
+    ///Blocks the route, or every route if `global` is set, until `retry_after` has passed
+    pub fn rate_limited(&self, route: &Route, retry_after: Duration, global: bool) {
+        let mut guard = self.rate_limits.write().expect("Rate limits poisoned");
+        if global {
+            let limit = guard.global.as_ref().map(|state| state.limit).unwrap_or(0);
+            guard.global = Some(RateLimitState::exhausted(limit, retry_after));
+        } else {
+            let key = guard.key(route);
+            let limit = guard.limits.get(&key).map(|state| state.limit).unwrap_or(0);
+            guard
+                .limits
+                .insert(key, RateLimitState::exhausted(limit, retry_after));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+
+    #[test]
+    fn routes_split_out_major_parameters() {
+        let route = Route::new(&Method::DELETE, "/channels/123/messages/456");
+        assert_eq!(
+            route.template,
+            "DELETE /channels/{channel_id}/messages/{id}"
+        );
+        assert_eq!(route.major_params, "123");
+
+        let route = Route::new(&Method::GET, "/channels/123/messages?limit=50");
+        assert_eq!(route.template, "GET /channels/{channel_id}/messages");
+
+        let route = Route::new(&Method::POST, "/webhooks/1/sometoken/messages/2");
+        assert_eq!(
+            route.template,
+            "POST /webhooks/{webhook_id}/{webhook_token}/messages/{id}"
+        );
+        assert_eq!(route.major_params, "1/sometoken");
+
+        let route = Route::new(&Method::POST, "/interactions/1/sometoken/callback");
+        assert_eq!(route.template, "POST /interactions/{id}/{token}/callback");
+
+        let route = Route::new(&Method::PUT, "/guilds/123/templates/abcDEF");
+        assert_eq!(route.template, "PUT /guilds/{guild_id}/templates/{code}");
+        assert_eq!(route.major_params, "123");
+    }
+
+    #[test]
+    fn buckets_are_shared_across_major_parameters() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("X-RateLimit-Limit", "5".parse().unwrap());
+        headers.insert("X-RateLimit-Remaining", "4".parse().unwrap());
+        headers.insert("X-RateLimit-Reset", "4102444800.000".parse().unwrap());
+        headers.insert("X-RateLimit-Bucket", "abc".parse().unwrap());
 
-        self.rate_limits.write().unwrap().insert(entry, rate_limit);
+        let limiter = RateLimiter::default();
+        for channel_id in 0..10 {
+            let route = Route::new(&Method::GET, &format!("/channels/{}/messages", channel_id));
+            limiter.update_limits(&route, &headers);
+        }
+        let guard = limiter.rate_limits.read().unwrap();
+        assert_eq!(guard.buckets_by_route.len(), 1);
+        assert_eq!(guard.limits.len(), 10);
+        assert!(guard.limits.contains_key("abc:3"));
     }
 }
@@ -0,0 +1,123 @@
+use super::paginate::{next_cursor, paginate};
+use super::Client;
+use crate::model::*;
+use crate::Error;
+use futures::Stream;
+
+impl Client {
+    pub async fn create_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &ReactionType,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}/@me",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0,
+            emoji = emoji.to_path_segment()
+        );
+        self.put(url, None).await
+    }
+
+    pub async fn delete_own_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &ReactionType,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}/@me",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0,
+            emoji = emoji.to_path_segment()
+        );
+        self.delete(url, None).await
+    }
+
+    pub async fn delete_user_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &ReactionType,
+        user_id: UserId,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}/{user_id}",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0,
+            emoji = emoji.to_path_segment(),
+            user_id = (user_id.0).0
+        );
+        self.delete(url, None).await
+    }
+
+    ///Users who reacted with `emoji`, ordered by user id. Pass the last id returned as `after` to get the next page
+    pub async fn get_reactions(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &ReactionType,
+        after: Option<UserId>,
+        limit: Option<u64>,
+    ) -> Result<Vec<User>, Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}?after={after}&limit={limit}",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0,
+            emoji = emoji.to_path_segment(),
+            after = after.map(|after| (after.0).0).unwrap_or(0),
+            limit = limit.unwrap_or(25)
+        );
+        self.get_json(url).await
+    }
+
+    ///Every user who reacted with `emoji`, ordered by user id, fetching further pages as needed
+    pub fn reaction_users(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: ReactionType,
+    ) -> impl Stream<Item = Result<User, Error>> + Send + 'static {
+        let client = self.clone();
+        paginate(None, move |after| {
+            let client = client.clone();
+            let emoji = emoji.clone();
+            async move {
+                let users = client
+                    .get_reactions(channel_id, message_id, &emoji, after, Some(100))
+                    .await?;
+                let next = next_cursor(&users, 100, |user| Some(user.id));
+                Ok((users, next))
+            }
+        })
+    }
+
+    pub async fn delete_all_reactions(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0
+        );
+        self.delete(url, None).await
+    }
+
+    pub async fn delete_all_reactions_for_emoji(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        emoji: &ReactionType,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/reactions/{emoji}",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0,
+            emoji = emoji.to_path_segment()
+        );
+        self.delete(url, None).await
+    }
+}
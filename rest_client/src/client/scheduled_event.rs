@@ -0,0 +1,207 @@
+use super::Client;
+use crate::model::*;
+use crate::Error;
+use chrono::{DateTime, FixedOffset};
+
+#[derive(Serialize)]
+pub struct NewScheduledEvent {
+    ///the stage or voice channel the event is hosted in, omitted for external events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+    ///the location of an external event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<ScheduledEventEntityMetadata>,
+    ///the name of the scheduled event
+    pub name: String,
+    ///the privacy level of the scheduled event
+    pub privacy_level: PrivacyLevel,
+    ///the time to schedule the scheduled event
+    pub scheduled_start_time: DateTime<FixedOffset>,
+    ///the time when the scheduled event is scheduled to end, required for external events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_time: Option<DateTime<FixedOffset>>,
+    ///the description of the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    ///the entity type of the scheduled event
+    pub entity_type: ScheduledEventEntityType,
+    ///the cover image of the scheduled event, as a data uri (see `image_data_uri`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+impl NewScheduledEvent {
+    ///An event hosted in a stage channel
+    pub fn stage<S: Into<String>>(
+        channel_id: ChannelId,
+        name: S,
+        scheduled_start_time: DateTime<FixedOffset>,
+    ) -> Self {
+        Self {
+            channel_id: Some(channel_id),
+            entity_metadata: None,
+            name: name.into(),
+            privacy_level: PrivacyLevel::GuildOnly,
+            scheduled_start_time,
+            scheduled_end_time: None,
+            description: None,
+            entity_type: ScheduledEventEntityType::StageInstance,
+            image: None,
+        }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct EditScheduledEvent {
+    ///the channel id of the scheduled event, set to `Some(None)` when changing to an external event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Option<ChannelId>>,
+    ///the entity metadata of the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<ScheduledEventEntityMetadata>,
+    ///the name of the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    ///the privacy level of the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy_level: Option<PrivacyLevel>,
+    ///the time to schedule the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_start_time: Option<DateTime<FixedOffset>>,
+    ///the time when the scheduled event is scheduled to end
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_end_time: Option<DateTime<FixedOffset>>,
+    ///the description of the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    ///the entity type of the scheduled event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<ScheduledEventEntityType>,
+    ///the status of the scheduled event, used to start, end or cancel it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ScheduledEventStatus>,
+    ///the cover image of the scheduled event, as a data uri (see `image_data_uri`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GetScheduledEventUsers {
+    ///number of users to return (up to maximum 100)
+    pub limit: Option<u64>,
+    ///include guild member data if it exists
+    pub with_member: bool,
+    ///consider only users before given user id
+    pub before: Option<UserId>,
+    ///consider only users after given user id
+    pub after: Option<UserId>,
+}
+
+impl GetScheduledEventUsers {
+    fn to_query(self) -> String {
+        let mut query = Vec::new();
+        if let Some(limit) = self.limit {
+            query.push(format!("limit={}", limit));
+        }
+        if self.with_member {
+            query.push("with_member=true".to_string());
+        }
+        if let Some(before) = self.before {
+            query.push(format!("before={}", (before.0).0));
+        }
+        if let Some(after) = self.after {
+            query.push(format!("after={}", (after.0).0));
+        }
+        query.join("&")
+    }
+}
+
+impl Client {
+    pub async fn list_scheduled_events(
+        &self,
+        guild_id: GuildId,
+        with_user_count: bool,
+    ) -> Result<Vec<GuildScheduledEvent>, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/scheduled-events?with_user_count={with_user_count}",
+            guild_id = (guild_id.0).0,
+            with_user_count = with_user_count
+        );
+        self.get_json(url).await
+    }
+
+    ///Requires MANAGE_EVENTS
+    pub async fn create_scheduled_event(
+        &self,
+        guild_id: GuildId,
+        event: NewScheduledEvent,
+        reason: Option<&str>,
+    ) -> Result<GuildScheduledEvent, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/scheduled-events",
+            guild_id = (guild_id.0).0
+        );
+        self.post_return_json(url, event, reason).await
+    }
+
+    pub async fn get_scheduled_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+        with_user_count: bool,
+    ) -> Result<GuildScheduledEvent, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/scheduled-events/{event_id}?with_user_count={with_user_count}",
+            guild_id = (guild_id.0).0,
+            event_id = (event_id.0).0,
+            with_user_count = with_user_count
+        );
+        self.get_json(url).await
+    }
+
+    ///Also used to start, end or cancel an event by setting its `status`
+    pub async fn modify_scheduled_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+        event: EditScheduledEvent,
+        reason: Option<&str>,
+    ) -> Result<GuildScheduledEvent, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/scheduled-events/{event_id}",
+            guild_id = (guild_id.0).0,
+            event_id = (event_id.0).0
+        );
+        self.patch_return_json(url, event, reason).await
+    }
+
+    pub async fn delete_scheduled_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/scheduled-events/{event_id}",
+            guild_id = (guild_id.0).0,
+            event_id = (event_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+
+    ///Users subscribed to an event, ordered by user id
+    pub async fn get_scheduled_event_users(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+        filter: GetScheduledEventUsers,
+    ) -> Result<Vec<GuildScheduledEventUser>, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/scheduled-events/{event_id}/users?{query}",
+            guild_id = (guild_id.0).0,
+            event_id = (event_id.0).0,
+            query = filter.to_query()
+        );
+        self.get_json(url).await
+    }
+}
@@ -0,0 +1,150 @@
+use super::Client;
+use crate::model::*;
+use crate::Error;
+use chrono::{DateTime, FixedOffset, Utc};
+
+#[derive(Serialize)]
+pub struct NewStageInstance {
+    ///the id of the stage channel
+    pub channel_id: ChannelId,
+    ///the topic of the stage instance (1-120 characters)
+    pub topic: String,
+    ///the privacy level of the stage instance (default GuildOnly)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy_level: Option<PrivacyLevel>,
+    ///notify @everyone that a stage instance has started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_start_notification: Option<bool>,
+}
+
+#[derive(Serialize, Default)]
+pub struct EditStageInstance {
+    ///the topic of the stage instance (1-120 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    ///the privacy level of the stage instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy_level: Option<PrivacyLevel>,
+}
+
+#[derive(Serialize)]
+pub struct EditCurrentUserVoiceState {
+    ///the id of the stage channel the user is currently in
+    pub channel_id: ChannelId,
+    ///toggles the user's suppress state, false moves the user to speaker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppress: Option<bool>,
+    ///sets the user's request to speak, `Some(None)` clears it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_to_speak_timestamp: Option<Option<DateTime<FixedOffset>>>,
+}
+
+impl EditCurrentUserVoiceState {
+    ///Raises the current user's hand in a stage channel
+    pub fn request_to_speak(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            suppress: None,
+            request_to_speak_timestamp: Some(Some(Utc::now().into())),
+        }
+    }
+
+    ///Moves the current user to speaker. Requires MUTE_MEMBERS
+    pub fn speak(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            suppress: Some(false),
+            request_to_speak_timestamp: None,
+        }
+    }
+
+    ///Moves the current user back to the audience
+    pub fn listen(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            suppress: Some(true),
+            request_to_speak_timestamp: Some(None),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct EditUserVoiceState {
+    ///the id of the stage channel the user is currently in
+    pub channel_id: ChannelId,
+    ///toggles the user's suppress state, false invites the user to speak
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppress: Option<bool>,
+}
+
+impl Client {
+    ///Starts a stage. Requires MANAGE_CHANNELS, MUTE_MEMBERS and MOVE_MEMBERS in the stage channel
+    pub async fn create_stage_instance(
+        &self,
+        stage: NewStageInstance,
+        reason: Option<&str>,
+    ) -> Result<StageInstance, Error> {
+        self.post_return_json("/stage-instances", stage, reason)
+            .await
+    }
+
+    pub async fn get_stage_instance(&self, channel_id: ChannelId) -> Result<StageInstance, Error> {
+        let url = format!(
+            "/stage-instances/{channel_id}",
+            channel_id = (channel_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn modify_stage_instance(
+        &self,
+        channel_id: ChannelId,
+        stage: EditStageInstance,
+        reason: Option<&str>,
+    ) -> Result<StageInstance, Error> {
+        let url = format!(
+            "/stage-instances/{channel_id}",
+            channel_id = (channel_id.0).0
+        );
+        self.patch_return_json(url, stage, reason).await
+    }
+
+    ///Ends a stage
+    pub async fn delete_stage_instance(
+        &self,
+        channel_id: ChannelId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/stage-instances/{channel_id}",
+            channel_id = (channel_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+
+    pub async fn modify_current_user_voice_state(
+        &self,
+        guild_id: GuildId,
+        state: EditCurrentUserVoiceState,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/voice-states/@me",
+            guild_id = (guild_id.0).0
+        );
+        self.patch_json(url, state, None).await
+    }
+
+    pub async fn modify_user_voice_state(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        state: EditUserVoiceState,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/voice-states/{user_id}",
+            guild_id = (guild_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.patch_json(url, state, None).await
+    }
+}
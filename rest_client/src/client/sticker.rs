@@ -0,0 +1,110 @@
+use super::attachment::{Attachment, Multipart};
+use super::{Client, RequestBody};
+use crate::model::*;
+use crate::Error;
+
+pub struct NewSticker {
+    ///name of the sticker (2-30 characters)
+    pub name: String,
+    ///description of the sticker (empty or 2-100 characters)
+    pub description: String,
+    ///autocomplete/suggestion tags for the sticker (max 200 characters)
+    pub tags: String,
+    ///the sticker's png, apng or lottie json file, max 500 KB
+    pub file: Attachment,
+}
+
+#[derive(Serialize, Default)]
+pub struct EditSticker {
+    ///name of the sticker (2-30 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    ///description of the sticker (2-100 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    ///autocomplete/suggestion tags for the sticker (max 200 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+}
+
+impl Client {
+    pub async fn get_sticker(&self, sticker_id: StickerId) -> Result<Sticker, Error> {
+        let url = format!("/stickers/{sticker_id}", sticker_id = (sticker_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn list_guild_stickers(&self, guild_id: GuildId) -> Result<Vec<Sticker>, Error> {
+        let url = format!("/guilds/{guild_id}/stickers", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn get_guild_sticker(
+        &self,
+        guild_id: GuildId,
+        sticker_id: StickerId,
+    ) -> Result<Sticker, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/stickers/{sticker_id}",
+            guild_id = (guild_id.0).0,
+            sticker_id = (sticker_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    ///Requires MANAGE_EMOJIS_AND_STICKERS
+    pub async fn create_guild_sticker(
+        &self,
+        guild_id: GuildId,
+        sticker: NewSticker,
+        reason: Option<&str>,
+    ) -> Result<Sticker, Error> {
+        let url = format!("/guilds/{guild_id}/stickers", guild_id = (guild_id.0).0);
+        let body = Multipart::form(
+            &[
+                ("name", &sticker.name),
+                ("description", &sticker.description),
+                ("tags", &sticker.tags),
+            ],
+            "file",
+            &sticker.file,
+        );
+        let res = self
+            .execute_request(
+                reqwest::Method::POST,
+                url,
+                RequestBody::Multipart(body),
+                reason,
+            )
+            .await?;
+        Ok(res.json().await?)
+    }
+
+    pub async fn modify_guild_sticker(
+        &self,
+        guild_id: GuildId,
+        sticker_id: StickerId,
+        sticker: EditSticker,
+        reason: Option<&str>,
+    ) -> Result<Sticker, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/stickers/{sticker_id}",
+            guild_id = (guild_id.0).0,
+            sticker_id = (sticker_id.0).0
+        );
+        self.patch_return_json(url, sticker, reason).await
+    }
+
+    pub async fn delete_guild_sticker(
+        &self,
+        guild_id: GuildId,
+        sticker_id: StickerId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/guilds/{guild_id}/stickers/{sticker_id}",
+            guild_id = (guild_id.0).0,
+            sticker_id = (sticker_id.0).0
+        );
+        self.delete(url, reason).await
+    }
+}
@@ -0,0 +1,64 @@
+use super::Client;
+use crate::model::*;
+use crate::Error;
+
+#[derive(Serialize)]
+pub struct NewGuildTemplate {
+    ///name of the template (1-100 characters)
+    pub name: String,
+    ///description for the template (0-120 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Client {
+    pub async fn get_guild_template(&self, code: &str) -> Result<GuildTemplate, Error> {
+        let url = format!("/guilds/templates/{code}", code = code);
+        self.get_json(url).await
+    }
+
+    pub async fn get_guild_templates(
+        &self,
+        guild_id: GuildId,
+    ) -> Result<Vec<GuildTemplate>, Error> {
+        let url = format!("/guilds/{guild_id}/templates", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    ///Creates a template from the guild's current state. Requires MANAGE_GUILD
+    pub async fn create_guild_template(
+        &self,
+        guild_id: GuildId,
+        template: NewGuildTemplate,
+    ) -> Result<GuildTemplate, Error> {
+        let url = format!("/guilds/{guild_id}/templates", guild_id = (guild_id.0).0);
+        self.post_return_json(url, template, None).await
+    }
+
+    ///Updates the template to the guild's current state. Requires MANAGE_GUILD
+    pub async fn sync_guild_template(
+        &self,
+        guild_id: GuildId,
+        code: &str,
+    ) -> Result<GuildTemplate, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/templates/{code}",
+            guild_id = (guild_id.0).0,
+            code = code
+        );
+        self.put_return_json(url, None).await
+    }
+
+    pub async fn delete_guild_template(
+        &self,
+        guild_id: GuildId,
+        code: &str,
+    ) -> Result<GuildTemplate, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/templates/{code}",
+            guild_id = (guild_id.0).0,
+            code = code
+        );
+        self.delete_return_json(url, None).await
+    }
+}
@@ -0,0 +1,191 @@
+use super::Client;
+use crate::model::*;
+use crate::Error;
+use chrono::{DateTime, FixedOffset};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+
+#[derive(Serialize)]
+pub struct NewThread {
+    ///thread name (1-100 characters)
+    pub name: String,
+    ///duration in minutes to automatically archive the thread after recent activity, can be set to: 60, 1440, 4320, 10080
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<u64>,
+    ///the type of thread to create, ignored when starting a thread from a message
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<ChannelType>,
+    ///whether non-moderators can add other non-moderators to a private thread
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invitable: Option<bool>,
+}
+
+impl NewThread {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            auto_archive_duration: None,
+            typ: None,
+            invitable: None,
+        }
+    }
+}
+
+impl Client {
+    ///Starts a thread attached to an existing message
+    pub async fn start_thread_with_message(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        thread: NewThread,
+        reason: Option<&str>,
+    ) -> Result<Channel, Error> {
+        let url = format!(
+            "/channels/{channel_id}/messages/{message_id}/threads",
+            channel_id = (channel_id.0).0,
+            message_id = (message_id.0).0
+        );
+        self.post_return_json(url, thread, reason).await
+    }
+
+    ///Starts a thread that isn't attached to a message, private unless `typ` says otherwise
+    pub async fn start_thread(
+        &self,
+        channel_id: ChannelId,
+        thread: NewThread,
+        reason: Option<&str>,
+    ) -> Result<Channel, Error> {
+        let url = format!(
+            "/channels/{channel_id}/threads",
+            channel_id = (channel_id.0).0
+        );
+        self.post_return_json(url, thread, reason).await
+    }
+
+    pub async fn join_thread(&self, channel_id: ChannelId) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/thread-members/@me",
+            channel_id = (channel_id.0).0
+        );
+        self.put(url, None).await
+    }
+
+    pub async fn leave_thread(&self, channel_id: ChannelId) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/thread-members/@me",
+            channel_id = (channel_id.0).0
+        );
+        self.delete(url, None).await
+    }
+
+    pub async fn add_thread_member(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/thread-members/{user_id}",
+            channel_id = (channel_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.put(url, None).await
+    }
+
+    pub async fn remove_thread_member(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/channels/{channel_id}/thread-members/{user_id}",
+            channel_id = (channel_id.0).0,
+            user_id = (user_id.0).0
+        );
+        self.delete(url, None).await
+    }
+
+    pub async fn get_thread_members(
+        &self,
+        channel_id: ChannelId,
+    ) -> Result<Vec<ThreadMember>, Error> {
+        let url = format!(
+            "/channels/{channel_id}/thread-members",
+            channel_id = (channel_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    ///All active threads in a guild, including private threads the bot can see
+    pub async fn list_active_threads(&self, guild_id: GuildId) -> Result<ThreadList, Error> {
+        let url = format!(
+            "/guilds/{guild_id}/threads/active",
+            guild_id = (guild_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    ///Archived public threads in a channel, most recently archived first
+    pub async fn list_public_archived_threads(
+        &self,
+        channel_id: ChannelId,
+        before: Option<DateTime<FixedOffset>>,
+        limit: Option<u64>,
+    ) -> Result<ThreadList, Error> {
+        let url = format!(
+            "/channels/{channel_id}/threads/archived/public?{query}",
+            channel_id = (channel_id.0).0,
+            query = archived_query(before, limit)
+        );
+        self.get_json(url).await
+    }
+
+    ///Archived private threads in a channel, most recently archived first. Requires MANAGE_THREADS
+    pub async fn list_private_archived_threads(
+        &self,
+        channel_id: ChannelId,
+        before: Option<DateTime<FixedOffset>>,
+        limit: Option<u64>,
+    ) -> Result<ThreadList, Error> {
+        let url = format!(
+            "/channels/{channel_id}/threads/archived/private?{query}",
+            channel_id = (channel_id.0).0,
+            query = archived_query(before, limit)
+        );
+        self.get_json(url).await
+    }
+
+    ///Archived private threads in a channel that the bot has joined, ordered by thread id descending
+    pub async fn list_joined_private_archived_threads(
+        &self,
+        channel_id: ChannelId,
+        before: Option<ChannelId>,
+        limit: Option<u64>,
+    ) -> Result<ThreadList, Error> {
+        let mut query = Vec::new();
+        if let Some(before) = before {
+            query.push(format!("before={}", (before.0).0));
+        }
+        if let Some(limit) = limit {
+            query.push(format!("limit={}", limit));
+        }
+        let url = format!(
+            "/channels/{channel_id}/users/@me/threads/archived/private?{query}",
+            channel_id = (channel_id.0).0,
+            query = query.join("&")
+        );
+        self.get_json(url).await
+    }
+}
+
+fn archived_query(before: Option<DateTime<FixedOffset>>, limit: Option<u64>) -> String {
+    let mut query = Vec::new();
+    if let Some(before) = before {
+        query.push(format!(
+            "before={}",
+            utf8_percent_encode(&before.to_rfc3339(), NON_ALPHANUMERIC)
+        ));
+    }
+    if let Some(limit) = limit {
+        query.push(format!("limit={}", limit));
+    }
+    query.join("&")
+}
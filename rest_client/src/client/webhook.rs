@@ -0,0 +1,334 @@
+use super::{Attachment, Client, ClientBuilder, RequestBody};
+use crate::model::*;
+use crate::Error;
+use url::Url;
+
+#[derive(Serialize)]
+pub struct NewWebhook {
+    ///name of the webhook (1-80 characters)
+    pub name: String,
+    ///image for the default webhook avatar, as a data uri (see `image_data_uri`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+}
+
+#[derive(Default, Serialize)]
+pub struct EditWebhook {
+    ///the default name of the webhook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    ///image for the default webhook avatar, as a data uri (see `image_data_uri`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+    ///the new channel id this webhook should be moved to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<ChannelId>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ExecuteWebhook {
+    ///the message contents (up to 2000 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    ///override the default username of the webhook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    ///override the default avatar of the webhook
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    ///true if this is a TTS message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    ///embedded rich content, up to 10 embeds
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub embeds: Vec<Embed>,
+    ///allowed mentions for the message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    ///send the message to this thread within the webhook's channel
+    #[serde(skip)]
+    pub thread_id: Option<ChannelId>,
+    ///files to attach to the message, sent as a multipart request
+    #[serde(skip)]
+    pub files: Vec<Attachment>,
+}
+
+impl ExecuteWebhook {
+    pub fn text<S: Into<String>>(s: S) -> Self {
+        Self {
+            content: Some(s.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_embed<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Embed),
+    {
+        let mut embed = Default::default();
+        f(&mut embed);
+        self.embeds.push(embed);
+        self
+    }
+
+    pub fn with_file(mut self, file: Attachment) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    pub fn enforce_embed_limits(&self) -> Result<(), EmbedTooBigError> {
+        for embed in &self.embeds {
+            embed.enforce_embed_limits()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Serialize)]
+pub struct EditWebhookMessage {
+    ///the message contents (up to 2000 characters)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    ///embedded rich content, replacing the existing embeds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    ///allowed mentions for the message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_mentions: Option<AllowedMentions>,
+    ///files to attach to the message, sent as a multipart request
+    #[serde(skip)]
+    pub files: Vec<Attachment>,
+}
+
+impl Client {
+    pub async fn create_webhook(
+        &self,
+        channel_id: ChannelId,
+        webhook: NewWebhook,
+        reason: Option<&str>,
+    ) -> Result<Webhook, Error> {
+        let url = format!(
+            "/channels/{channel_id}/webhooks",
+            channel_id = (channel_id.0).0
+        );
+        self.post_return_json(url, webhook, reason).await
+    }
+
+    pub async fn get_channel_webhooks(&self, channel_id: ChannelId) -> Result<Vec<Webhook>, Error> {
+        let url = format!(
+            "/channels/{channel_id}/webhooks",
+            channel_id = (channel_id.0).0
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn get_guild_webhooks(&self, guild_id: GuildId) -> Result<Vec<Webhook>, Error> {
+        let url = format!("/guilds/{guild_id}/webhooks", guild_id = (guild_id.0).0);
+        self.get_json(url).await
+    }
+
+    pub async fn get_webhook(&self, webhook_id: WebhookId) -> Result<Webhook, Error> {
+        let url = format!("/webhooks/{webhook_id}", webhook_id = (webhook_id.0).0);
+        self.get_json(url).await
+    }
+
+    ///Gets a webhook using its token, which doesn't require authentication
+    pub async fn get_webhook_with_token(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+    ) -> Result<Webhook, Error> {
+        let url = format!(
+            "/webhooks/{webhook_id}/{token}",
+            webhook_id = (webhook_id.0).0,
+            token = token
+        );
+        self.get_json(url).await
+    }
+
+    pub async fn modify_webhook(
+        &self,
+        webhook_id: WebhookId,
+        edit: EditWebhook,
+        reason: Option<&str>,
+    ) -> Result<Webhook, Error> {
+        let url = format!("/webhooks/{webhook_id}", webhook_id = (webhook_id.0).0);
+        self.patch_return_json(url, edit, reason).await
+    }
+
+    pub async fn delete_webhook(
+        &self,
+        webhook_id: WebhookId,
+        reason: Option<&str>,
+    ) -> Result<(), Error> {
+        let url = format!("/webhooks/{webhook_id}", webhook_id = (webhook_id.0).0);
+        self.delete(url, reason).await
+    }
+
+    ///Sends a message through a webhook. The message is only returned if `wait` is set,
+    ///which also makes discord confirm the message was saved before responding
+    pub async fn execute_webhook(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+        mut message: ExecuteWebhook,
+        wait: bool,
+    ) -> Result<Option<Message>, Error> {
+        message.enforce_embed_limits()?;
+        let url = format!(
+            "/webhooks/{webhook_id}/{token}?wait={wait}{thread_id}",
+            webhook_id = (webhook_id.0).0,
+            token = token,
+            wait = wait,
+            thread_id = message
+                .thread_id
+                .map(|thread_id| format!("&thread_id={}", (thread_id.0).0))
+                .unwrap_or_default()
+        );
+        let files = std::mem::take(&mut message.files);
+        let body = RequestBody::with_files(&message, &files)?;
+        let res = self
+            .execute_request(reqwest::Method::POST, url, body, None)
+            .await?;
+        if wait {
+            Ok(Some(res.json().await?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn edit_webhook_message(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+        message_id: MessageId,
+        mut edit: EditWebhookMessage,
+    ) -> Result<Message, Error> {
+        let url = format!(
+            "/webhooks/{webhook_id}/{token}/messages/{message_id}",
+            webhook_id = (webhook_id.0).0,
+            token = token,
+            message_id = (message_id.0).0
+        );
+        let files = std::mem::take(&mut edit.files);
+        self.send_with_files(reqwest::Method::PATCH, url, edit, files)
+            .await
+    }
+
+    pub async fn delete_webhook_message(
+        &self,
+        webhook_id: WebhookId,
+        token: &str,
+        message_id: MessageId,
+    ) -> Result<(), Error> {
+        let url = format!(
+            "/webhooks/{webhook_id}/{token}/messages/{message_id}",
+            webhook_id = (webhook_id.0).0,
+            token = token,
+            message_id = (message_id.0).0
+        );
+        self.delete(url, None).await
+    }
+}
+
+///Executes a single webhook using only its id and token, without needing a bot token
+#[derive(Clone)]
+pub struct WebhookClient {
+    client: Client,
+    webhook_id: WebhookId,
+    token: String,
+}
+
+impl WebhookClient {
+    pub fn new<S: Into<String>>(webhook_id: WebhookId, token: S) -> Result<Self, Error> {
+        Self::with_builder(webhook_id, token, ClientBuilder::unauthenticated())
+    }
+
+    ///Uses a customised http client. The builder's token is replaced by the webhook token
+    pub fn with_builder<S: Into<String>>(
+        webhook_id: WebhookId,
+        token: S,
+        builder: ClientBuilder,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            client: builder.without_token().build()?,
+            webhook_id,
+            token: token.into(),
+        })
+    }
+
+    ///Parses a webhook url such as `https://discord.com/api/webhooks/{id}/{token}`
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        let (webhook_id, token) = parse_webhook_url(url)?;
+        Self::new(webhook_id, token)
+    }
+
+    pub fn webhook_id(&self) -> WebhookId {
+        self.webhook_id
+    }
+
+    pub async fn get(&self) -> Result<Webhook, Error> {
+        self.client
+            .get_webhook_with_token(self.webhook_id, &self.token)
+            .await
+    }
+
+    pub async fn execute(
+        &self,
+        message: ExecuteWebhook,
+        wait: bool,
+    ) -> Result<Option<Message>, Error> {
+        self.client
+            .execute_webhook(self.webhook_id, &self.token, message, wait)
+            .await
+    }
+
+    pub async fn edit_message(
+        &self,
+        message_id: MessageId,
+        edit: EditWebhookMessage,
+    ) -> Result<Message, Error> {
+        self.client
+            .edit_webhook_message(self.webhook_id, &self.token, message_id, edit)
+            .await
+    }
+
+    pub async fn delete_message(&self, message_id: MessageId) -> Result<(), Error> {
+        self.client
+            .delete_webhook_message(self.webhook_id, &self.token, message_id)
+            .await
+    }
+}
+
+fn parse_webhook_url(url: &str) -> Result<(WebhookId, String), Error> {
+    let invalid = || Error::InvalidWebhookUrl(url.to_owned());
+    let parsed = Url::parse(url)?;
+    let mut segments = parsed
+        .path_segments()
+        .ok_or_else(invalid)?
+        .skip_while(|segment| *segment != "webhooks")
+        .skip(1);
+    let webhook_id = segments
+        .next()
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(invalid)?;
+    let token = segments
+        .next()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(invalid)?;
+    Ok((WebhookId(Snowflake(webhook_id)), token.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_webhook_urls() {
+        let (id, token) =
+            parse_webhook_url("https://discord.com/api/v9/webhooks/1234/abc-DEF_123").unwrap();
+        assert_eq!(id, WebhookId(Snowflake(1234)));
+        assert_eq!(token, "abc-DEF_123");
+        assert!(parse_webhook_url("https://discord.com/api/webhooks/1234").is_err());
+    }
+}
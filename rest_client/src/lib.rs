@@ -6,11 +6,14 @@ use thiserror::Error;
 
 pub use discord_next_model as model;
 
+mod api_error;
+pub use api_error::*;
 mod client;
 pub use client::*;
 pub mod util;
 
-pub(crate) const API_BASE: &str = "https://discordapp.com/api/v9";
+pub const DEFAULT_BASE_URL: &str = "https://discord.com/api";
+pub const DEFAULT_API_VERSION: u8 = 9;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -24,14 +27,37 @@ pub enum Error {
     EmbedTooBig(#[from] model::EmbedTooBigError),
     #[error("Was rate limited too many times (>={0}) while executing: {1}")]
     TooManyRetries(u16, String),
+    #[error("An io error occurred {0:?}")]
+    Io(#[from] std::io::Error),
     #[error("An error with a timer operation for ratelimiting {0:?}")]
     Timer(#[from] tokio::time::Error),
     #[error("A non success response code was returned from an http request: {0:?}")]
     UnsuccessfulHttp(http::StatusCode),
+    #[error("{0}")]
+    DiscordApi(#[from] DiscordApiError),
+    #[error("Not a valid webhook url: {0}")]
+    InvalidWebhookUrl(String),
+    #[error("Image data wasn't a png, jpeg, gif or webp image")]
+    UnknownImageFormat,
     #[error("An error while building an http data structure {0:?}")]
     HttpBuilderError(#[from] http::Error),
 }
 
+impl Error {
+    ///The error discord returned, if this error came from a failed api request
+    pub fn api_error(&self) -> Option<&DiscordApiError> {
+        match self {
+            Error::DiscordApi(api_error) => Some(api_error),
+            _other => None,
+        }
+    }
+
+    ///The discord JSON error code, if this error came from a failed api request
+    pub fn api_error_code(&self) -> Option<JsonErrorCode> {
+        self.api_error().map(|api_error| api_error.code)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     //commented because tokio currently doesn't have a way of getting return values from the runtime.